@@ -9,8 +9,10 @@ use axum::{
 use crate::{
     http::error::ApiError,
     models::{
+        event::Event,
         generic::{Page, Pageable},
-        job::{Job, JobPayload},
+        job::{CreateJobQuery, Job, JobPayload, JobStatus},
+        job_log::JobLog,
     },
     state::AppState,
 };
@@ -20,6 +22,9 @@ pub fn routes() -> Router<Arc<AppState>> {
         .route("/", routing::get(list))
         .route("/:id", routing::get(get))
         .route("/", routing::post(create))
+        .route("/:id/cancel", routing::post(cancel))
+        .route("/:id/retry", routing::post(retry))
+        .route("/:id/logs", routing::get(logs))
 }
 
 async fn list(
@@ -36,10 +41,49 @@ async fn get(
     Ok(Json(state.job_service.find(id).await?))
 }
 
+async fn logs(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+    Query(query): Query<Pageable>,
+) -> Result<Json<Page<JobLog>>, ApiError> {
+    state.job_service.find(id).await?;
+    Ok(Json(state.job_log_service.find_page(id, &query).await?))
+}
+
 async fn create(
     State(state): State<Arc<AppState>>,
+    Query(query): Query<CreateJobQuery>,
     Json(body): Json<JobPayload>,
 ) -> Result<Json<Job>, ApiError> {
-    let job = state.job_service.enqueue(body).await?;
+    let job = state.job_service.enqueue_user(body, &query).await?;
+    Ok(Json(job))
+}
+
+async fn cancel(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> Result<Json<Job>, ApiError> {
+    state.job_service.find(id).await?;
+    if !state.job_service.cancel(id).await? {
+        return Err(ApiError::Conflict("Job is not pending or running".into()));
+    }
+    let job = state.job_service.find(id).await?;
+    if job.status == JobStatus::Cancelled {
+        state
+            .event_service
+            .send(Event::JobEnd { job: job.clone() })
+            .ok();
+    }
     Ok(Json(job))
 }
+
+async fn retry(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> Result<Json<Job>, ApiError> {
+    let job = state.job_service.find(id).await?;
+    if job.status != JobStatus::Failed {
+        return Err(ApiError::Conflict("Only failed jobs can be retried".into()));
+    }
+    Ok(Json(state.job_service.retry(&job).await?))
+}
@@ -9,10 +9,13 @@ use axum::{
 use crate::{
     http::error::ApiError,
     models::{
+        blocklist::DeleteLyricsQuery,
         generic::Page,
+        job::{CreateJobQuery, Job, JobPayload},
         lyrics::{Lyrics, LyricsContent, LyricsQuery},
     },
     state::AppState,
+    worker::jobs::publish_lyrics::PublishLyricsParams,
 };
 
 pub fn routes() -> Router<Arc<AppState>> {
@@ -21,6 +24,7 @@ pub fn routes() -> Router<Arc<AppState>> {
         .route("/:id", routing::get(get))
         .route("/:id", routing::delete(delete))
         .route("/:id/content", routing::get(get_content))
+        .route("/:id/publish", routing::post(publish))
 }
 
 pub async fn list(
@@ -40,7 +44,21 @@ pub async fn get(
 pub async fn delete(
     State(state): State<Arc<AppState>>,
     Path(id): Path<i64>,
+    Query(query): Query<DeleteLyricsQuery>,
 ) -> Result<(), ApiError> {
+    if query.blocklist {
+        let lyrics = state.lyrics_service.find(id).await?;
+        let (Some(provider), Some(identifier)) = (lyrics.provider, lyrics.provider_identifier)
+        else {
+            return Err(ApiError::BadRequest(
+                "Lyrics without a provider result can't be blocklisted".into(),
+            ));
+        };
+        state
+            .blocklist_service
+            .add(lyrics.track.id, &provider, &identifier)
+            .await?;
+    }
     Ok(state.lyrics_service.remove(id).await?)
 }
 
@@ -50,3 +68,18 @@ pub async fn get_content(
 ) -> Result<Json<LyricsContent>, ApiError> {
     Ok(Json(state.lyrics_service.get_content(id).await?))
 }
+
+pub async fn publish(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> Result<Json<Job>, ApiError> {
+    state.lyrics_service.find(id).await?;
+    let job = state
+        .job_service
+        .enqueue_user(
+            JobPayload::PublishLyrics(PublishLyricsParams { lyrics_id: id }),
+            &CreateJobQuery::default(),
+        )
+        .await?;
+    Ok(Json(job))
+}
@@ -2,24 +2,58 @@ use std::sync::Arc;
 
 use axum::{
     extract::{Query, State},
-    routing::get,
+    routing::{delete, get, post},
     Json, Router,
 };
 
 use crate::{
     http::error::ApiError,
-    models::provider::{ProviderResult, ResultsQuery},
+    models::{
+        job::{CreateJobQuery, Job, JobPayload},
+        provider::{CachePurgeQuery, ProviderResults, ProviderStatus, ResultsQuery},
+    },
     state::AppState,
+    worker::jobs::import_result::ImportResultParams,
 };
 
 pub fn routes() -> Router<Arc<AppState>> {
-    Router::new().route("/results", get(list_results))
+    Router::new()
+        .route("/", get(list_statuses))
+        .route("/results", get(list_results))
+        .route("/import", post(import_result))
+        .route("/cache", delete(purge_cache))
+}
+
+async fn list_statuses(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<Vec<ProviderStatus>>, ApiError> {
+    Ok(Json(state.provider_service.statuses()))
 }
 
 async fn list_results(
     State(state): State<Arc<AppState>>,
     Query(query): Query<ResultsQuery>,
-) -> Result<Json<Vec<ProviderResult>>, ApiError> {
+) -> Result<Json<ProviderResults>, ApiError> {
     let track = state.track_service.find(query.track_id).await?;
-    Ok(Json(state.provider_service.get_results(&track).await?))
+    Ok(Json(state.provider_service.get_results(&track).await))
+}
+
+async fn import_result(
+    State(state): State<Arc<AppState>>,
+    Json(body): Json<ImportResultParams>,
+) -> Result<Json<Job>, ApiError> {
+    state.track_service.find(body.track_id).await?;
+    let job = state
+        .job_service
+        .enqueue_user(JobPayload::ImportResult(body), &CreateJobQuery::default())
+        .await?;
+    Ok(Json(job))
+}
+
+async fn purge_cache(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<CachePurgeQuery>,
+) -> Result<(), ApiError> {
+    state.cache_service.purge(query.provider.as_deref()).await?;
+    Ok(())
 }
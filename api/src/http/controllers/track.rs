@@ -9,8 +9,10 @@ use axum::{
 use crate::{
     http::error::ApiError,
     models::{
-        generic::Page,
-        track::{Track, TracksQuery},
+        blocklist::BlocklistEntry,
+        generic::{Page, Pageable},
+        track::{Track, TracksQuery, UpdateTrackPin},
+        track_search::TrackSearch,
     },
     state::AppState,
 };
@@ -19,6 +21,14 @@ pub fn routes() -> Router<Arc<AppState>> {
     Router::new()
         .route("/", routing::get(list))
         .route("/:id", routing::get(get))
+        .route("/:id/pin", routing::put(update_pin))
+        .route("/:id/searches", routing::get(list_searches))
+        .route("/:id/blocklist", routing::get(list_blocklist))
+        .route("/:id/blocklist", routing::delete(clear_blocklist))
+        .route(
+            "/:id/blocklist/:entry_id",
+            routing::delete(delete_blocklist_entry),
+        )
 }
 
 async fn list(
@@ -34,3 +44,50 @@ async fn get(
 ) -> Result<Json<Track>, ApiError> {
     Ok(Json(state.track_service.find(id).await?))
 }
+
+async fn update_pin(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+    Json(body): Json<UpdateTrackPin>,
+) -> Result<Json<Track>, ApiError> {
+    state
+        .track_service
+        .set_lyrics_pinned(id, body.pinned)
+        .await?;
+    Ok(Json(state.track_service.find(id).await?))
+}
+
+async fn list_searches(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+    Query(pageable): Query<Pageable>,
+) -> Result<Json<Page<TrackSearch>>, ApiError> {
+    Ok(Json(
+        state.track_search_service.find_page(id, &pageable).await?,
+    ))
+}
+
+async fn list_blocklist(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> Result<Json<Vec<BlocklistEntry>>, ApiError> {
+    Ok(Json(state.blocklist_service.find_by_track(id).await?))
+}
+
+async fn clear_blocklist(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> Result<(), ApiError> {
+    state.blocklist_service.clear(id).await?;
+    Ok(())
+}
+
+async fn delete_blocklist_entry(
+    State(state): State<Arc<AppState>>,
+    Path((id, entry_id)): Path<(i64, i64)>,
+) -> Result<(), ApiError> {
+    match state.blocklist_service.remove(id, entry_id).await? {
+        0 => Err(ApiError::NotFound()),
+        _ => Ok(()),
+    }
+}
@@ -12,6 +12,12 @@ pub enum ApiError {
     #[error("Forbidden error")]
     Forbidden(),
 
+    #[error("Bad request: {0}")]
+    BadRequest(String),
+
+    #[error("Conflict error: {0}")]
+    Conflict(String),
+
     #[error("Database error: {0}")]
     Db(#[from] sqlx::Error),
 
@@ -24,6 +30,8 @@ impl IntoResponse for ApiError {
         let status = match self {
             ApiError::NotFound() => StatusCode::NOT_FOUND,
             ApiError::Forbidden() => StatusCode::FORBIDDEN,
+            ApiError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            ApiError::Conflict(_) => StatusCode::CONFLICT,
             ApiError::Db(_) => StatusCode::INTERNAL_SERVER_ERROR,
             ApiError::Other(_) => StatusCode::INTERNAL_SERVER_ERROR,
         };
@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlocklistEntry {
+    pub id: i64,
+    pub created_at: String,
+    pub track_id: i64,
+    pub provider: String,
+    pub identifier: String,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteLyricsQuery {
+    #[serde(default)]
+    pub blocklist: bool,
+}
@@ -1,4 +1,8 @@
-use crate::models::{job::Job, lyrics::Lyrics};
+use crate::models::{
+    job::{Job, JobProgress},
+    job_log::JobLogLevel,
+    lyrics::Lyrics,
+};
 use serde::Serialize;
 
 #[derive(Debug, Clone, Serialize)]
@@ -8,7 +12,13 @@ pub enum Event {
     #[serde(rename_all = "camelCase")]
     JobStart { job: Job },
     #[serde(rename_all = "camelCase")]
-    JobLog { job_id: i64, log: String },
+    JobLog {
+        job_id: i64,
+        level: JobLogLevel,
+        log: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    JobProgress { job_id: i64, progress: JobProgress },
     #[serde(rename_all = "camelCase")]
     JobEnd { job: Job },
 
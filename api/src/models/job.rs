@@ -1,25 +1,40 @@
 use core::fmt;
-use std::{str::FromStr, sync::Arc};
+use std::{
+    str::FromStr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    models::event::Event,
+    models::{
+        event::Event,
+        job_log::{CreateJobLog, JobLogLevel},
+    },
     state::AppState,
-    worker::jobs::{
-        clean_album::{clean_album, CleanAlbumParams},
-        import_lyrics::{import_lyrics, ImportLyricsParams},
-        scan_album::{scan_album, ScanAlbumParams},
-        scan_artist::{scan_artist, ScanArtistParams},
-        scan_library::scan_library,
-        scan_track::{scan_track, ScanTrackParams},
-        search_album::{search_album, SearchAlbumParams},
-        search_artist::{search_artist, SearchArtistParams},
-        search_library::search_library,
-        search_track::{search_track, SearchTrackParams},
-        sync_artist::{sync_artist, SyncArtistParams},
-        sync_library::sync_library,
+    worker::{
+        guard::{is_database_busy, is_retryable},
+        jobs::{
+            clean_album::{clean_album, CleanAlbumParams},
+            import_lyrics::{import_lyrics, ImportLyricsParams},
+            import_result::{import_result, ImportResultParams},
+            publish_lyrics::{publish_lyrics, PublishLyricsParams},
+            scan_album::{scan_album, ScanAlbumParams},
+            scan_artist::{scan_artist, ScanArtistParams},
+            scan_library::scan_library,
+            scan_track::{scan_track, ScanTrackParams},
+            search_album::{search_album, SearchAlbumParams},
+            search_artist::{search_artist, SearchArtistParams},
+            search_library::search_library,
+            search_track::{search_track, SearchTrackParams},
+            search_wanted::search_wanted,
+            sync_artist::{sync_artist, SyncArtistParams},
+            sync_library::sync_library,
+        },
     },
 };
 
@@ -31,6 +46,10 @@ pub enum JobPayload {
 
     // Import
     ImportLyrics(ImportLyricsParams),
+    ImportResult(ImportResultParams),
+
+    // Publish
+    PublishLyrics(PublishLyricsParams),
 
     // Scan
     ScanLibrary,
@@ -43,12 +62,153 @@ pub enum JobPayload {
     SearchArtist(SearchArtistParams),
     SearchAlbum(SearchAlbumParams),
     SearchTrack(SearchTrackParams),
+    SearchWanted,
 
     // Sync
     SyncLibrary,
     SyncArtist(SyncArtistParams),
 }
 
+impl JobPayload {
+    pub fn lane(&self) -> JobLane {
+        match self {
+            JobPayload::SearchLibrary
+            | JobPayload::SearchArtist(_)
+            | JobPayload::SearchAlbum(_)
+            | JobPayload::SearchTrack(_)
+            | JobPayload::SearchWanted => JobLane::Search,
+            _ => JobLane::Library,
+        }
+    }
+
+    pub fn priority(&self) -> i64 {
+        match self {
+            JobPayload::ScanLibrary
+            | JobPayload::SearchLibrary
+            | JobPayload::SearchWanted
+            | JobPayload::SyncLibrary => 0,
+            JobPayload::ScanArtist(_) | JobPayload::SearchArtist(_) | JobPayload::SyncArtist(_) => {
+                10
+            }
+            JobPayload::CleanAlbum(_) | JobPayload::ScanAlbum(_) | JobPayload::SearchAlbum(_) => 20,
+            JobPayload::ImportLyrics(_)
+            | JobPayload::ImportResult(_)
+            | JobPayload::PublishLyrics(_)
+            | JobPayload::ScanTrack(_)
+            | JobPayload::SearchTrack(_) => 30,
+        }
+    }
+
+    pub fn retry_policy(&self) -> RetryPolicy {
+        match self {
+            JobPayload::SyncLibrary | JobPayload::SyncArtist(_) => RetryPolicy {
+                max_attempts: 3,
+                backoff: 60,
+                retryable: |error| is_retryable(error) || is_database_busy(error),
+            },
+            JobPayload::ImportResult(_)
+            | JobPayload::PublishLyrics(_)
+            | JobPayload::SearchArtist(_)
+            | JobPayload::SearchAlbum(_)
+            | JobPayload::SearchTrack(_) => RetryPolicy {
+                max_attempts: 3,
+                backoff: 30,
+                retryable: is_retryable,
+            },
+            _ => RetryPolicy {
+                max_attempts: 1,
+                backoff: 0,
+                retryable: |_| false,
+            },
+        }
+    }
+
+    pub fn entity(&self) -> JobEntity {
+        match self {
+            JobPayload::CleanAlbum(p) => JobEntity::Album(p.album_id),
+
+            JobPayload::ImportLyrics(p) => JobEntity::Track(p.track_id),
+            JobPayload::ImportResult(p) => JobEntity::Track(p.track_id),
+
+            JobPayload::PublishLyrics(p) => JobEntity::Lyrics(p.lyrics_id),
+
+            JobPayload::ScanLibrary => JobEntity::Library,
+            JobPayload::ScanArtist(p) => JobEntity::Artist(p.artist_id),
+            JobPayload::ScanAlbum(p) => JobEntity::Album(p.album_id),
+            JobPayload::ScanTrack(p) => JobEntity::Track(p.track_id),
+
+            JobPayload::SearchLibrary | JobPayload::SearchWanted => JobEntity::Library,
+            JobPayload::SearchArtist(p) => JobEntity::Artist(p.artist_id),
+            JobPayload::SearchAlbum(p) => JobEntity::Album(p.album_id),
+            JobPayload::SearchTrack(p) => JobEntity::Track(p.track_id),
+
+            JobPayload::SyncLibrary => JobEntity::Library,
+            JobPayload::SyncArtist(p) => JobEntity::Artist(p.artist_id),
+        }
+    }
+}
+
+pub const USER_PRIORITY: i64 = 100;
+
+/// What a job works on. Jobs never run alongside another job on the same entity or on one of
+/// its parents, e.g. a library sync excludes every other job and an artist scan excludes the
+/// searches of that artist's tracks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobEntity {
+    Library,
+    Artist(i64),
+    Album(i64),
+    Track(i64),
+    Lyrics(i64),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    pub max_attempts: i64,
+    pub backoff: u64,
+    /// Whether a failure is transient for this job type and worth another attempt.
+    pub retryable: fn(&anyhow::Error) -> bool,
+}
+
+impl RetryPolicy {
+    pub fn delay_after(&self, attempt: i64) -> u64 {
+        let exponent = (attempt - 1).clamp(0, 16) as u32;
+        self.backoff.saturating_mul(1 << exponent)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum JobLane {
+    Library,
+    Search,
+    User,
+}
+
+impl fmt::Display for JobLane {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            JobLane::Library => "library",
+            JobLane::Search => "search",
+            JobLane::User => "user",
+        };
+        f.write_str(s)
+    }
+}
+
+impl FromStr for JobLane {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "library" => Ok(JobLane::Library),
+            "search" => Ok(JobLane::Search),
+            "user" => Ok(JobLane::User),
+            _ => Err(anyhow::anyhow!("invalid job lane")),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum JobStatus {
@@ -56,6 +216,7 @@ pub enum JobStatus {
     Running,
     Done,
     Failed,
+    Cancelled,
 }
 
 impl fmt::Display for JobStatus {
@@ -65,6 +226,7 @@ impl fmt::Display for JobStatus {
             JobStatus::Running => "running",
             JobStatus::Done => "done",
             JobStatus::Failed => "failed",
+            JobStatus::Cancelled => "cancelled",
         };
         f.write_str(s)
     }
@@ -79,6 +241,7 @@ impl FromStr for JobStatus {
             "running" => Ok(JobStatus::Running),
             "done" => Ok(JobStatus::Done),
             "failed" => Ok(JobStatus::Failed),
+            "cancelled" => Ok(JobStatus::Cancelled),
             _ => Err(anyhow::anyhow!("invalid job status")),
         }
     }
@@ -91,13 +254,90 @@ pub struct Job {
     pub created_at: String,
     pub payload: JobPayload,
     pub status: JobStatus,
+    pub lane: JobLane,
+    pub priority: i64,
     pub error: Option<String>,
+    pub progress: Option<JobProgress>,
+    pub attempt: i64,
+    pub max_attempts: i64,
+    pub retry_of: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobProgress {
+    pub current: usize,
+    pub total: usize,
+    pub phase: String,
+    pub entity: Option<String>,
+}
+
+impl JobProgress {
+    pub fn new<S: Into<String>>(current: usize, total: usize, phase: S) -> Self {
+        Self {
+            current,
+            total,
+            phase: phase.into(),
+            entity: None,
+        }
+    }
+
+    pub fn with_entity<S: Into<String>>(mut self, entity: S) -> Self {
+        self.entity = Some(entity.into());
+        self
+    }
+}
+
+impl fmt::Display for JobProgress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}/{}] {}", self.current, self.total, self.phase)?;
+        if let Some(entity) = &self.entity {
+            write!(f, ": {}", entity)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateJobQuery {
+    #[serde(default)]
+    pub force: bool,
+    pub priority: Option<i64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateJob {
+    pub payload: JobPayload,
+    pub lane: JobLane,
+    pub priority: i64,
+    pub attempt: i64,
+    pub max_attempts: i64,
+    pub retry_of: Option<i64>,
+    pub delay: Option<u64>,
+}
+
+impl CreateJob {
+    pub fn new(payload: JobPayload, lane: JobLane) -> Self {
+        let max_attempts = payload.retry_policy().max_attempts;
+        let priority = payload.priority();
+        Self {
+            payload,
+            lane,
+            priority,
+            attempt: 1,
+            max_attempts,
+            retry_of: None,
+            delay: None,
+        }
+    }
 }
 
 impl Job {
     async fn dispatch<T: Clone, F, Fut>(
         &self,
         state: &Arc<AppState>,
+        cancel: &CancellationToken,
         params: &T,
         f: F,
     ) -> Result<()>
@@ -108,55 +348,171 @@ impl Job {
         let context = JobContext {
             job_id: self.id,
             state: state.clone(),
+            cancel: cancel.clone(),
             params: params.clone(),
+            nested: false,
         };
-        f(context).await
+        CURRENT_JOB
+            .scope(context.clone_with_params(()), f(context))
+            .await
     }
 
-    pub async fn run(&self, state: &Arc<AppState>) -> Result<()> {
+    pub async fn run(&self, state: &Arc<AppState>, cancel: &CancellationToken) -> Result<()> {
         match &self.payload {
-            JobPayload::CleanAlbum(p) => self.dispatch(state, p, clean_album).await,
+            JobPayload::CleanAlbum(p) => self.dispatch(state, cancel, p, clean_album).await,
 
-            JobPayload::ImportLyrics(p) => self.dispatch(state, p, import_lyrics).await,
+            JobPayload::ImportLyrics(p) => self.dispatch(state, cancel, p, import_lyrics).await,
+            JobPayload::ImportResult(p) => self.dispatch(state, cancel, p, import_result).await,
 
-            JobPayload::ScanLibrary => self.dispatch(state, &(), scan_library).await,
-            JobPayload::ScanArtist(p) => self.dispatch(state, p, scan_artist).await,
-            JobPayload::ScanAlbum(p) => self.dispatch(state, p, scan_album).await,
-            JobPayload::ScanTrack(p) => self.dispatch(state, p, scan_track).await,
+            JobPayload::PublishLyrics(p) => self.dispatch(state, cancel, p, publish_lyrics).await,
 
-            JobPayload::SearchLibrary => self.dispatch(state, &(), search_library).await,
-            JobPayload::SearchArtist(p) => self.dispatch(state, p, search_artist).await,
-            JobPayload::SearchAlbum(p) => self.dispatch(state, p, search_album).await,
-            JobPayload::SearchTrack(p) => self.dispatch(state, p, search_track).await,
+            JobPayload::ScanLibrary => self.dispatch(state, cancel, &(), scan_library).await,
+            JobPayload::ScanArtist(p) => self.dispatch(state, cancel, p, scan_artist).await,
+            JobPayload::ScanAlbum(p) => self.dispatch(state, cancel, p, scan_album).await,
+            JobPayload::ScanTrack(p) => self.dispatch(state, cancel, p, scan_track).await,
 
-            JobPayload::SyncLibrary => self.dispatch(state, &(), sync_library).await,
+            JobPayload::SearchLibrary => self.dispatch(state, cancel, &(), search_library).await,
+            JobPayload::SearchArtist(p) => self.dispatch(state, cancel, p, search_artist).await,
+            JobPayload::SearchAlbum(p) => self.dispatch(state, cancel, p, search_album).await,
+            JobPayload::SearchTrack(p) => self.dispatch(state, cancel, p, search_track).await,
+            JobPayload::SearchWanted => self.dispatch(state, cancel, &(), search_wanted).await,
 
-            JobPayload::SyncArtist(p) => self.dispatch(state, p, sync_artist).await,
+            JobPayload::SyncLibrary => self.dispatch(state, cancel, &(), sync_library).await,
+
+            JobPayload::SyncArtist(p) => self.dispatch(state, cancel, p, sync_artist).await,
         }
     }
 }
 
+tokio::task_local! {
+    static CURRENT_JOB: JobContext<()>;
+}
+
+pub fn log_current_job<S: Into<String>>(log: S) {
+    match CURRENT_JOB.try_with(|context| context.clone_with_params(())) {
+        Ok(context) => context.log(log),
+        Err(_) => println!("{}", log.into()),
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Job cancelled")]
+pub struct JobCancelled;
+
+/// Whether a job stopped at a cancellation check, as opposed to failing after a cancel.
+pub fn is_cancelled(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| cause.is::<JobCancelled>())
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
 pub struct JobContext<T> {
     pub job_id: i64,
     pub state: Arc<AppState>,
+    pub cancel: CancellationToken,
     pub params: T,
+    /// Set when a job runs another job's function inline, e.g. a library sync syncing each
+    /// artist. Only the top-level job reports progress.
+    pub nested: bool,
 }
 
 impl<T> JobContext<T> {
+    pub fn check_cancelled(&self) -> Result<()> {
+        if self.cancel.is_cancelled() {
+            return Err(JobCancelled.into());
+        }
+        Ok(())
+    }
+
     pub fn log<S: Into<String>>(&self, log: S) {
+        self.log_with_level(JobLogLevel::Info, log)
+    }
+
+    pub fn warn<S: Into<String>>(&self, log: S) {
+        self.log_with_level(JobLogLevel::Warn, log)
+    }
+
+    pub fn error<S: Into<String>>(&self, log: S) {
+        self.log_with_level(JobLogLevel::Error, log)
+    }
+
+    fn log_with_level<S: Into<String>>(&self, level: JobLogLevel, log: S) {
         let log_s = log.into();
         println!("[job #{}] {}", self.job_id, &log_s);
+        self.state.job_log_service.push(CreateJobLog {
+            job_id: self.job_id,
+            level,
+            message: log_s.clone(),
+        });
         let _ = self.state.event_service.send(Event::JobLog {
             job_id: self.job_id,
+            level,
             log: log_s,
         });
     }
 
+    pub async fn progress(&self, progress: JobProgress) {
+        if self.nested {
+            return;
+        }
+        if let Err(e) = self
+            .state
+            .job_service
+            .update_progress(self.job_id, &progress)
+            .await
+        {
+            println!("[job #{}] Error while saving progress: {}", self.job_id, e);
+        }
+        let _ = self.state.event_service.send(Event::JobProgress {
+            job_id: self.job_id,
+            progress,
+        });
+    }
+
     pub fn clone_with_params<U>(&self, params: U) -> JobContext<U> {
         JobContext {
             job_id: self.job_id,
             state: self.state.clone(),
+            cancel: self.cancel.clone(),
             params,
+            nested: true,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_cancelled() {
+        assert!(is_cancelled(&JobCancelled.into()));
+        assert!(is_cancelled(
+            &anyhow::Error::from(JobCancelled).context("Searching album")
+        ));
+        assert!(!is_cancelled(&anyhow::anyhow!("database is locked")));
+    }
+
+    #[test]
+    fn test_retry_backoff() {
+        let policy = RetryPolicy {
+            max_attempts: 4,
+            backoff: 30,
+            retryable: |_| true,
+        };
+        assert_eq!(policy.delay_after(1), 30);
+        assert_eq!(policy.delay_after(2), 60);
+        assert_eq!(policy.delay_after(3), 120);
+    }
+}
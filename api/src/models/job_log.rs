@@ -0,0 +1,53 @@
+use core::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum JobLogLevel {
+    Info,
+    Warn,
+    Error,
+}
+
+impl fmt::Display for JobLogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            JobLogLevel::Info => "info",
+            JobLogLevel::Warn => "warn",
+            JobLogLevel::Error => "error",
+        };
+        f.write_str(s)
+    }
+}
+
+impl FromStr for JobLogLevel {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "info" => Ok(JobLogLevel::Info),
+            "warn" => Ok(JobLogLevel::Warn),
+            "error" => Ok(JobLogLevel::Error),
+            _ => Err(anyhow::anyhow!("invalid job log level")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobLog {
+    pub id: i64,
+    pub created_at: String,
+    pub job_id: i64,
+    pub level: JobLogLevel,
+    pub message: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateJobLog {
+    pub job_id: i64,
+    pub level: JobLogLevel,
+    pub message: String,
+}
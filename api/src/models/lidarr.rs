@@ -63,6 +63,9 @@ pub struct LidarrTrack {
     pub id: i64,
     pub artist_id: i64,
     pub foreign_track_id: Option<String>,
+    pub foreign_recording_id: Option<String>,
+    #[serde(default)]
+    pub isrcs: Vec<String>,
     pub track_file_id: i64,
     pub album_id: i64,
     pub absolute_track_number: i64,
@@ -70,6 +73,12 @@ pub struct LidarrTrack {
     pub title: String,
 }
 
+impl LidarrTrack {
+    pub fn isrc(&self) -> Option<&String> {
+        self.isrcs.iter().find(|isrc| !isrc.trim().is_empty())
+    }
+}
+
 #[derive(Serialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct LidarrTrackFileQuery {
@@ -12,6 +12,9 @@ pub struct Lyrics {
     pub file_path: String,
     pub checksum: String,
     pub provider: Option<String>,
+    pub provider_identifier: Option<String>,
+    pub published_at: Option<String>,
+    pub publish_error: Option<String>,
     pub track: Track,
 }
 
@@ -24,6 +27,7 @@ pub struct LyricsContent {
 pub struct CreateLyrics {
     pub language: Option<String>,
     pub provider: Option<String>,
+    pub provider_identifier: Option<String>,
     pub synced: bool,
     pub file_path: String,
     pub checksum: String,
@@ -1,8 +1,10 @@
 pub mod album;
 pub mod artist;
+pub mod blocklist;
 pub mod event;
 pub mod generic;
 pub mod job;
+pub mod job_log;
 pub mod lidarr;
 pub mod lyrics;
 pub mod notifier;
@@ -13,3 +15,4 @@ pub mod stats;
 pub mod status;
 pub mod token;
 pub mod track;
+pub mod track_search;
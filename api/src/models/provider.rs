@@ -3,17 +3,24 @@ use axum::async_trait;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    models::track::Track,
-    worker::{provider::LyricsProvider, providers::lrclib::LrcLibProvider},
+    models::{settings::ProvidersSettings, track::Track},
+    worker::{
+        provider::LyricsProvider,
+        providers::{
+            genius::GeniusProvider, local::LocalProvider, lrclib::LrcLibProvider,
+            musixmatch::MusixmatchProvider, netease::NeteaseProvider, script::ScriptProvider,
+            template::TemplateProvider,
+        },
+    },
 };
 
-#[derive(Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ProviderMetadata {
     pub name: String,
 }
 
-#[derive(Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ProviderFile {
     pub identifier: String,
@@ -22,40 +29,152 @@ pub struct ProviderFile {
     pub artist_name: String,
     pub album_title: String,
     pub synced: bool,
+    #[serde(default)]
+    pub exact: bool,
+    #[serde(default)]
+    pub translation: bool,
     pub duration_ms: Option<i64>,
+    #[serde(default)]
+    pub isrc: Option<String>,
+    #[serde(default)]
+    pub musicbrainz_id: Option<String>,
     pub content: Option<String>,
 }
 
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScoreBreakdown {
+    pub track_name: f64,
+    pub artist_name: f64,
+    pub album_title: f64,
+    pub duration: f64,
+    pub identifier: bool,
+}
+
 #[derive(Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ProviderResult {
     pub provider: ProviderMetadata,
     pub file: ProviderFile,
     pub score: f64,
+    pub breakdown: ScoreBreakdown,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderError {
+    pub provider: ProviderMetadata,
+    pub error: String,
+}
+
+#[derive(Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderResults {
+    pub providers: Vec<ProviderMetadata>,
+    pub results: Vec<ProviderResult>,
+    pub errors: Vec<ProviderError>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CircuitState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderStatus {
+    pub name: String,
+    pub state: CircuitState,
+    pub consecutive_failures: u32,
+    pub retry_after_secs: Option<u64>,
+    pub last_error: Option<String>,
 }
 
 pub enum Provider {
+    Genius(GeniusProvider),
+    Local(LocalProvider),
     LrcLib(LrcLibProvider),
+    Musixmatch(MusixmatchProvider),
+    Netease(NeteaseProvider),
+    Script(ScriptProvider),
+    Template(TemplateProvider),
 }
 
 #[async_trait]
 impl LyricsProvider for Provider {
     fn name(&self) -> &'static str {
         match self {
+            Self::Genius(provider) => provider.name(),
+            Self::Local(provider) => provider.name(),
             Self::LrcLib(provider) => provider.name(),
+            Self::Musixmatch(provider) => provider.name(),
+            Self::Netease(provider) => provider.name(),
+            Self::Script(provider) => provider.name(),
+            Self::Template(provider) => provider.name(),
+        }
+    }
+
+    fn remote(&self) -> bool {
+        match self {
+            Self::Genius(provider) => provider.remote(),
+            Self::Local(provider) => provider.remote(),
+            Self::LrcLib(provider) => provider.remote(),
+            Self::Musixmatch(provider) => provider.remote(),
+            Self::Netease(provider) => provider.remote(),
+            Self::Script(provider) => provider.remote(),
+            Self::Template(provider) => provider.remote(),
+        }
+    }
+
+    fn enabled(&self, settings: &ProvidersSettings) -> bool {
+        match self {
+            Self::Genius(provider) => provider.enabled(settings),
+            Self::Local(provider) => provider.enabled(settings),
+            Self::LrcLib(provider) => provider.enabled(settings),
+            Self::Musixmatch(provider) => provider.enabled(settings),
+            Self::Netease(provider) => provider.enabled(settings),
+            Self::Script(provider) => provider.enabled(settings),
+            Self::Template(provider) => provider.enabled(settings),
+        }
+    }
+
+    fn cache_scope(&self, settings: &ProvidersSettings) -> String {
+        match self {
+            Self::Genius(provider) => provider.cache_scope(settings),
+            Self::Local(provider) => provider.cache_scope(settings),
+            Self::LrcLib(provider) => provider.cache_scope(settings),
+            Self::Musixmatch(provider) => provider.cache_scope(settings),
+            Self::Netease(provider) => provider.cache_scope(settings),
+            Self::Script(provider) => provider.cache_scope(settings),
+            Self::Template(provider) => provider.cache_scope(settings),
         }
     }
 
     async fn search_lyrics(&self, track: &Track) -> Result<Vec<ProviderFile>> {
         match self {
+            Self::Genius(provider) => provider.search_lyrics(track),
+            Self::Local(provider) => provider.search_lyrics(track),
             Self::LrcLib(provider) => provider.search_lyrics(track),
+            Self::Musixmatch(provider) => provider.search_lyrics(track),
+            Self::Netease(provider) => provider.search_lyrics(track),
+            Self::Script(provider) => provider.search_lyrics(track),
+            Self::Template(provider) => provider.search_lyrics(track),
         }
         .await
     }
 
     async fn download(&self, result: &ProviderFile) -> Result<String> {
         match self {
+            Self::Genius(provider) => provider.download(result),
+            Self::Local(provider) => provider.download(result),
             Self::LrcLib(provider) => provider.download(result),
+            Self::Musixmatch(provider) => provider.download(result),
+            Self::Netease(provider) => provider.download(result),
+            Self::Script(provider) => provider.download(result),
+            Self::Template(provider) => provider.download(result),
         }
         .await
     }
@@ -66,3 +185,9 @@ impl LyricsProvider for Provider {
 pub struct ResultsQuery {
     pub track_id: i64,
 }
+
+#[derive(Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CachePurgeQuery {
+    pub provider: Option<String>,
+}
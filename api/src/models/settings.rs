@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::models::job::JobLane;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default, rename_all = "camelCase")]
 pub struct Settings {
@@ -7,6 +9,11 @@ pub struct Settings {
     pub auth: AuthSettings,
     pub lidarr: LidarrSettings,
     pub lyrics: LyricsSettings,
+    pub providers: ProvidersSettings,
+    pub cache: CacheSettings,
+    pub wanted: WantedSettings,
+    pub workers: WorkersSettings,
+    pub job_logs: JobLogsSettings,
 }
 
 impl Default for Settings {
@@ -16,6 +23,11 @@ impl Default for Settings {
             auth: AuthSettings::default(),
             lidarr: LidarrSettings::default(),
             lyrics: LyricsSettings::default(),
+            providers: ProvidersSettings::default(),
+            cache: CacheSettings::default(),
+            wanted: WantedSettings::default(),
+            workers: WorkersSettings::default(),
+            job_logs: JobLogsSettings::default(),
         }
     }
 }
@@ -66,6 +78,9 @@ impl Default for LidarrSettings {
 pub struct LyricsSettings {
     pub min_score: f64,
     pub upgrade: bool,
+    pub provider_timeout: u64,
+    pub weights: ScoreWeights,
+    pub normalization: NormalizationSettings,
 }
 
 impl Default for LyricsSettings {
@@ -73,6 +88,304 @@ impl Default for LyricsSettings {
         Self {
             min_score: 0.75,
             upgrade: true,
+            provider_timeout: 30,
+            weights: ScoreWeights::default(),
+            normalization: NormalizationSettings::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ScoreWeights {
+    pub track_name: f64,
+    pub artist_name: f64,
+    pub album_title: f64,
+    pub duration: f64,
+}
+
+impl Default for ScoreWeights {
+    fn default() -> Self {
+        Self {
+            track_name: 0.2,
+            artist_name: 0.2,
+            album_title: 0.2,
+            duration: 0.4,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NormalizationSettings {
+    pub lowercase: bool,
+    pub strip_punctuation: bool,
+    pub strip_features: bool,
+    pub strip_suffixes: bool,
+    pub strip_diacritics: bool,
+}
+
+impl Default for NormalizationSettings {
+    fn default() -> Self {
+        Self {
+            lowercase: true,
+            strip_punctuation: true,
+            strip_features: true,
+            strip_suffixes: true,
+            strip_diacritics: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CacheSettings {
+    pub enabled: bool,
+    pub hit_ttl: u64,
+    pub miss_ttl: u64,
+}
+
+impl Default for CacheSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            hit_ttl: 7 * 24 * 60 * 60,
+            miss_ttl: 24 * 60 * 60,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct WantedSettings {
+    pub budget: u32,
+    pub concurrency: usize,
+    pub backoff: Vec<u64>,
+}
+
+impl WantedSettings {
+    pub fn backoff_after(&self, attempts: i64) -> u64 {
+        let index = (attempts.max(1) - 1) as usize;
+        self.backoff
+            .get(index)
+            .or(self.backoff.last())
+            .copied()
+            .unwrap_or(0)
+    }
+}
+
+impl Default for WantedSettings {
+    fn default() -> Self {
+        Self {
+            budget: 50,
+            concurrency: 4,
+            backoff: vec![
+                24 * 60 * 60,
+                3 * 24 * 60 * 60,
+                7 * 24 * 60 * 60,
+                30 * 24 * 60 * 60,
+            ],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct WorkersSettings {
+    pub library: usize,
+    pub search: usize,
+    pub user: usize,
+}
+
+impl WorkersSettings {
+    pub fn size(&self, lane: JobLane) -> usize {
+        match lane {
+            JobLane::Library => self.library,
+            JobLane::Search => self.search,
+            JobLane::User => self.user,
+        }
+        .max(1)
+    }
+}
+
+impl Default for WorkersSettings {
+    fn default() -> Self {
+        Self {
+            library: 1,
+            search: 2,
+            user: 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct JobLogsSettings {
+    pub retention_days: u64,
+    pub max_per_job: u32,
+}
+
+impl Default for JobLogsSettings {
+    fn default() -> Self {
+        Self {
+            retention_days: 14,
+            max_per_job: 1000,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ProvidersSettings {
+    pub limits: ProviderLimitsSettings,
+    pub genius: GeniusSettings,
+    pub local: LocalSettings,
+    pub musixmatch: MusixmatchSettings,
+    pub netease: NeteaseSettings,
+    pub script: ScriptSettings,
+    pub template: TemplateSettings,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ProviderLimitsSettings {
+    pub requests_per_second: f64,
+    pub max_in_flight: usize,
+    pub max_retries: u32,
+    pub retry_backoff_ms: u64,
+    pub failure_threshold: u32,
+    pub cooldown: u64,
+}
+
+impl Default for ProviderLimitsSettings {
+    fn default() -> Self {
+        Self {
+            requests_per_second: 2.0,
+            max_in_flight: 2,
+            max_retries: 3,
+            retry_backoff_ms: 500,
+            failure_threshold: 5,
+            cooldown: 300,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct GeniusSettings {
+    pub enabled: bool,
+    pub access_token: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct LocalSettings {
+    pub enabled: bool,
+    pub path: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct MusixmatchSettings {
+    pub enabled: bool,
+    pub token: Option<String>,
+    pub enhanced: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NeteaseSettings {
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ScriptSettings {
+    pub enabled: bool,
+    pub path: String,
+    pub timeout: u64,
+}
+
+impl Default for ScriptSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: "".into(),
+            timeout: 30,
         }
     }
 }
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct TemplateSettings {
+    pub enabled: bool,
+    pub search_url: String,
+    pub results_pointer: String,
+    pub fields: TemplateFieldsSettings,
+    pub download_url: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct TemplateFieldsSettings {
+    pub identifier: String,
+    pub name: String,
+    pub track_name: String,
+    pub artist_name: String,
+    pub album_title: String,
+    pub duration: String,
+    pub duration_unit: TemplateDurationUnit,
+    pub isrc: String,
+    pub musicbrainz_id: String,
+    pub synced_lyrics: String,
+    pub plain_lyrics: String,
+}
+
+impl Default for TemplateFieldsSettings {
+    fn default() -> Self {
+        Self {
+            identifier: "/id".into(),
+            name: "".into(),
+            track_name: "/trackName".into(),
+            artist_name: "/artistName".into(),
+            album_title: "/albumName".into(),
+            duration: "/duration".into(),
+            duration_unit: TemplateDurationUnit::Seconds,
+            isrc: "".into(),
+            musicbrainz_id: "".into(),
+            synced_lyrics: "/syncedLyrics".into(),
+            plain_lyrics: "/plainLyrics".into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TemplateDurationUnit {
+    #[default]
+    Seconds,
+    Milliseconds,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wanted_backoff() {
+        let settings = WantedSettings {
+            backoff: vec![60, 600],
+            ..WantedSettings::default()
+        };
+        assert_eq!(settings.backoff_after(1), 60);
+        assert_eq!(settings.backoff_after(2), 600);
+        assert_eq!(settings.backoff_after(12), 600);
+
+        let settings = WantedSettings {
+            backoff: Vec::new(),
+            ..WantedSettings::default()
+        };
+        assert_eq!(settings.backoff_after(3), 0);
+    }
+}
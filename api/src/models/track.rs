@@ -14,7 +14,11 @@ pub struct Track {
     pub title: String,
     pub file_path: String,
     pub duration_ms: i64,
+    pub musicbrainz_id: Option<String>,
+    pub recording_musicbrainz_id: Option<String>,
+    pub isrc: Option<String>,
     pub has_lyrics: bool,
+    pub lyrics_pinned: bool,
     pub album: AlbumWithArtist,
     pub artist: Artist,
 }
@@ -48,3 +52,9 @@ pub struct TracksQuery {
     #[serde(flatten)]
     pub filters: TracksFilters,
 }
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateTrackPin {
+    pub pinned: bool,
+}
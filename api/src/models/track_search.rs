@@ -0,0 +1,109 @@
+use core::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::provider::{ProviderError, ProviderResult, ScoreBreakdown};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SearchOutcome {
+    Imported,
+    ImportFailed,
+    NoResults,
+    ProviderError,
+    BelowMinScore,
+    Blocklisted,
+}
+
+impl fmt::Display for SearchOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            SearchOutcome::Imported => "imported",
+            SearchOutcome::ImportFailed => "importFailed",
+            SearchOutcome::NoResults => "noResults",
+            SearchOutcome::ProviderError => "providerError",
+            SearchOutcome::BelowMinScore => "belowMinScore",
+            SearchOutcome::Blocklisted => "blocklisted",
+        };
+        f.write_str(s)
+    }
+}
+
+impl FromStr for SearchOutcome {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "imported" => Ok(SearchOutcome::Imported),
+            "importFailed" => Ok(SearchOutcome::ImportFailed),
+            "noResults" => Ok(SearchOutcome::NoResults),
+            "providerError" => Ok(SearchOutcome::ProviderError),
+            "belowMinScore" => Ok(SearchOutcome::BelowMinScore),
+            "blocklisted" => Ok(SearchOutcome::Blocklisted),
+            _ => Err(anyhow::anyhow!("invalid search outcome")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchCandidate {
+    pub provider: String,
+    pub identifier: String,
+    pub name: String,
+    pub track_name: String,
+    pub artist_name: String,
+    pub album_title: String,
+    pub synced: bool,
+    pub duration_ms: Option<i64>,
+    pub score: f64,
+    pub breakdown: ScoreBreakdown,
+    pub blocklisted: bool,
+}
+
+impl SearchCandidate {
+    pub fn new(result: &ProviderResult, blocklisted: bool) -> Self {
+        Self {
+            provider: result.provider.name.clone(),
+            identifier: result.file.identifier.clone(),
+            name: result.file.name.clone(),
+            track_name: result.file.track_name.clone(),
+            artist_name: result.file.artist_name.clone(),
+            album_title: result.file.album_title.clone(),
+            synced: result.file.synced,
+            duration_ms: result.file.duration_ms,
+            score: result.score,
+            breakdown: result.breakdown.clone(),
+            blocklisted,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrackSearch {
+    pub id: i64,
+    pub created_at: String,
+    pub track_id: i64,
+    pub job_id: Option<i64>,
+    pub outcome: SearchOutcome,
+    pub min_score: f64,
+    pub providers: Vec<String>,
+    pub candidates: Vec<SearchCandidate>,
+    pub errors: Vec<ProviderError>,
+    pub chosen: Option<SearchCandidate>,
+    pub error: Option<String>,
+}
+
+pub struct CreateTrackSearch {
+    pub track_id: i64,
+    pub job_id: Option<i64>,
+    pub outcome: SearchOutcome,
+    pub min_score: f64,
+    pub providers: Vec<String>,
+    pub candidates: Vec<SearchCandidate>,
+    pub errors: Vec<ProviderError>,
+    pub chosen: Option<SearchCandidate>,
+    pub error: Option<String>,
+}
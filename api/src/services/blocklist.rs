@@ -0,0 +1,127 @@
+use anyhow::Result;
+use sqlx::{prelude::FromRow, types::time::OffsetDateTime, Pool, Sqlite};
+
+use crate::models::blocklist::BlocklistEntry;
+
+#[derive(FromRow)]
+struct BlocklistRow {
+    id: i64,
+    created_at: OffsetDateTime,
+    track_id: i64,
+    provider: String,
+    identifier: String,
+}
+
+impl From<BlocklistRow> for BlocklistEntry {
+    fn from(value: BlocklistRow) -> Self {
+        Self {
+            id: value.id,
+            created_at: value.created_at.to_string(),
+            track_id: value.track_id,
+            provider: value.provider,
+            identifier: value.identifier,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct BlocklistService {
+    pool: Pool<Sqlite>,
+}
+
+impl BlocklistService {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+
+    pub async fn find_by_track(&self, track_id: i64) -> Result<Vec<BlocklistEntry>> {
+        let rows = sqlx::query_as!(
+            BlocklistRow,
+            r#"SELECT
+                b."id",
+                b."created_at",
+                b."track_id",
+                b."provider",
+                b."identifier"
+            FROM lyrics_blocklist b
+            WHERE b."track_id" = $1
+            ORDER BY b."created_at" DESC, b."id" DESC"#,
+            track_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().map(BlocklistEntry::from).collect())
+    }
+
+    pub async fn add(&self, track_id: i64, provider: &str, identifier: &str) -> Result<()> {
+        sqlx::query!(
+            r#"INSERT INTO lyrics_blocklist (
+                "track_id",
+                "provider",
+                "identifier"
+            ) VALUES (
+                $1, $2, $3
+            ) ON CONFLICT ("track_id", "provider", "identifier") DO NOTHING"#,
+            track_id,
+            provider,
+            identifier
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn remove(&self, track_id: i64, id: i64) -> Result<u64> {
+        let result = sqlx::query!(
+            r#"DELETE FROM lyrics_blocklist
+            WHERE "track_id" = $1 AND "id" = $2"#,
+            track_id,
+            id
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn clear(&self, track_id: i64) -> Result<u64> {
+        let result = sqlx::query!(
+            r#"DELETE FROM lyrics_blocklist
+            WHERE "track_id" = $1"#,
+            track_id
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::fixtures;
+
+    async fn service() -> BlocklistService {
+        BlocklistService::new(fixtures::pool().await)
+    }
+
+    #[tokio::test]
+    async fn test_add_and_clear() {
+        let blocklist = service().await;
+        blocklist.add(1, "LrcLib", "42").await.unwrap();
+        blocklist.add(1, "LrcLib", "42").await.unwrap();
+        blocklist.add(1, "Genius", "42").await.unwrap();
+        blocklist.add(2, "LrcLib", "42").await.unwrap();
+
+        let entries = blocklist.find_by_track(1).await.unwrap();
+        assert_eq!(entries.len(), 2);
+        assert!(entries
+            .iter()
+            .any(|entry| entry.provider == "LrcLib" && entry.identifier == "42"));
+
+        assert_eq!(blocklist.remove(2, entries[0].id).await.unwrap(), 0);
+        assert_eq!(blocklist.remove(1, entries[0].id).await.unwrap(), 1);
+        assert_eq!(blocklist.clear(1).await.unwrap(), 1);
+        assert!(blocklist.find_by_track(1).await.unwrap().is_empty());
+        assert_eq!(blocklist.find_by_track(2).await.unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,129 @@
+use anyhow::Result;
+use serde::{de::DeserializeOwned, Serialize};
+use sqlx::{Pool, Sqlite};
+
+#[derive(Clone)]
+pub struct CacheService {
+    pool: Pool<Sqlite>,
+}
+
+impl CacheService {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+
+    pub async fn get<T: DeserializeOwned>(&self, provider: &str, key: &str) -> Result<Option<T>> {
+        let row = sqlx::query!(
+            r#"SELECT "value" FROM provider_cache
+            WHERE "provider" = $1 AND "key" = $2 AND "expires_at" > datetime('now')"#,
+            provider,
+            key
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(match row {
+            Some(row) => Some(serde_json::from_str(&row.value)?),
+            None => None,
+        })
+    }
+
+    pub async fn set<T: Serialize>(
+        &self,
+        provider: &str,
+        key: &str,
+        value: &T,
+        ttl: u64,
+    ) -> Result<()> {
+        let value_string = serde_json::to_string(value)?;
+        let ttl_modifier = format!("+{} seconds", ttl);
+        sqlx::query!(
+            r#"INSERT INTO provider_cache (
+                "provider",
+                "key",
+                "expires_at",
+                "value"
+            ) VALUES (
+                $1,
+                $2,
+                datetime('now', $3),
+                $4
+            ) ON CONFLICT ("provider", "key") DO UPDATE SET
+                "created_at" = CURRENT_TIMESTAMP,
+                "expires_at" = excluded."expires_at",
+                "value" = excluded."value""#,
+            provider,
+            key,
+            ttl_modifier,
+            value_string
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn purge(&self, provider: Option<&str>) -> Result<u64> {
+        let result = sqlx::query!(
+            r#"DELETE FROM provider_cache
+            WHERE $1 IS NULL OR "provider" = $1"#,
+            provider
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn purge_expired(&self) -> Result<u64> {
+        let result = sqlx::query!(
+            r#"DELETE FROM provider_cache
+            WHERE "expires_at" <= datetime('now')"#
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::fixtures;
+
+    async fn service() -> CacheService {
+        CacheService::new(fixtures::pool().await)
+    }
+
+    #[tokio::test]
+    async fn test_get_set() {
+        let cache = service().await;
+        assert_eq!(cache.get::<String>("LrcLib", "key").await.unwrap(), None);
+
+        cache.set("LrcLib", "key", &"value", 60).await.unwrap();
+        cache.set("LrcLib", "key", &"updated", 60).await.unwrap();
+        assert_eq!(
+            cache
+                .get::<String>("LrcLib", "key")
+                .await
+                .unwrap()
+                .as_deref(),
+            Some("updated")
+        );
+        assert_eq!(cache.get::<String>("Genius", "key").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_expired() {
+        let cache = service().await;
+        cache.set("LrcLib", "key", &"value", 0).await.unwrap();
+        assert_eq!(cache.get::<String>("LrcLib", "key").await.unwrap(), None);
+        assert_eq!(cache.purge_expired().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_purge() {
+        let cache = service().await;
+        cache.set("LrcLib", "a", &"value", 60).await.unwrap();
+        cache.set("Genius", "b", &"value", 60).await.unwrap();
+        assert_eq!(cache.purge(Some("Genius")).await.unwrap(), 1);
+        assert_eq!(cache.purge(None).await.unwrap(), 1);
+    }
+}
@@ -1,16 +1,24 @@
-use std::sync::Arc;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
 
 use anyhow::Result;
-use sqlx::{prelude::FromRow, Pool, QueryBuilder, Sqlite};
-use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use sqlx::{prelude::FromRow, Executor, Pool, QueryBuilder, Sqlite};
+use tokio::sync::Notify;
 
 use crate::{
     models::{
         event::Event,
         generic::{IdRow, Page, Pageable, TotalRow},
-        job::{Job, JobPayload, JobStatus},
+        job::{
+            is_cancelled, CancellationToken, CreateJob, CreateJobQuery, Job, JobContext, JobEntity,
+            JobLane, JobPayload, JobProgress, JobStatus, USER_PRIORITY,
+        },
     },
     state::AppState,
+    worker::pool::{WorkerPermit, WorkerPool},
 };
 
 #[derive(FromRow)]
@@ -19,7 +27,13 @@ pub struct JobRow {
     created_at: String,
     payload: String,
     status: String,
+    lane: String,
+    priority: i64,
     error: Option<String>,
+    progress: Option<String>,
+    attempt: i64,
+    max_attempts: i64,
+    retry_of: Option<i64>,
 }
 
 impl TryFrom<JobRow> for Job {
@@ -31,7 +45,16 @@ impl TryFrom<JobRow> for Job {
             created_at: value.created_at,
             payload: serde_json::from_str(&value.payload)?,
             status: value.status.parse()?,
+            lane: value.lane.parse()?,
+            priority: value.priority,
             error: value.error,
+            progress: value
+                .progress
+                .map(|progress| serde_json::from_str(&progress))
+                .transpose()?,
+            attempt: value.attempt,
+            max_attempts: value.max_attempts,
+            retry_of: value.retry_of,
         })
     }
 }
@@ -41,47 +64,237 @@ const SELECT: &str = r#"SELECT
     j."created_at",
     j."payload",
     j."status",
-    j."error"
+    j."lane",
+    j."priority",
+    j."error",
+    j."progress",
+    j."attempt",
+    j."max_attempts",
+    j."retry_of"
 FROM job j"#;
 
-#[derive(Clone)]
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// Pending jobs fetched per lane on each wakeup. Jobs further down the queue are picked up on
+/// later wakeups as running ones finish.
+const PENDING_BATCH: i64 = 50;
+
 pub struct JobService {
     pool: Pool<Sqlite>,
-    sender: UnboundedSender<Job>,
+    notify: Notify,
+    running: Mutex<HashMap<i64, CancellationToken>>,
 }
 
 impl JobService {
-    pub fn new(pool: Pool<Sqlite>) -> (Self, UnboundedReceiver<Job>) {
-        let (sender, reciever) = mpsc::unbounded_channel();
-        (Self { pool, sender }, reciever)
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self {
+            pool,
+            notify: Notify::new(),
+            running: Mutex::new(HashMap::new()),
+        }
     }
 
-    pub async fn start_worker(state: Arc<AppState>, mut receiver: UnboundedReceiver<Job>) {
-        while let Some(mut job) = receiver.recv().await {
-            state
-                .job_service
-                .update_status(job.id, JobStatus::Running)
+    pub async fn start_worker(state: Arc<AppState>) {
+        match state.job_service.fail_interrupted().await {
+            Ok(0) => {}
+            Ok(count) => println!("Failed {} interrupted job(s) out of attempts", count),
+            Err(e) => println!("Error while failing interrupted jobs: {:}", e),
+        }
+        match state.job_service.requeue_running().await {
+            Ok(0) => {}
+            Ok(count) => println!("Re-queued {} interrupted job(s)", count),
+            Err(e) => println!("Error while re-queuing interrupted jobs: {:}", e),
+        }
+
+        let pool = WorkerPool::new();
+        loop {
+            let workers = state.settings_service.get().await.workers;
+            match state.job_service.find_pending(PENDING_BATCH).await {
+                Ok(jobs) => {
+                    for job in jobs {
+                        let key = match state.job_service.lock_key(job.payload.entity()).await {
+                            Ok(key) => key,
+                            Err(e) => {
+                                println!("Error while resolving job #{} entity: {:}", job.id, e);
+                                continue;
+                            }
+                        };
+                        if let Some(permit) =
+                            pool.try_acquire(job.lane, workers.size(job.lane), &key)
+                        {
+                            Self::dispatch(&state, permit, job);
+                        }
+                    }
+                }
+                Err(e) => println!("Error while fetching pending jobs: {:}", e),
+            }
+            tokio::time::timeout(POLL_INTERVAL, state.job_service.notify.notified())
                 .await
                 .ok();
-            job.status = JobStatus::Running;
-            state
-                .event_service
-                .send(Event::JobStart { job: job.clone() })
-                .ok();
-            if let Err(e) = job.run(&state).await {
-                println!("Error while running job: {:}", e);
+        }
+    }
+
+    /// Path of the entity and its parents, e.g. `artist:1/album:2/track:3/`, so that the worker
+    /// pool can keep jobs on an entity and on its parents apart. Library-wide jobs only lock
+    /// against each other, so a long sync doesn't hold back single-track jobs.
+    async fn lock_key(&self, entity: JobEntity) -> Result<String> {
+        let key = match entity {
+            JobEntity::Library => String::from("library/"),
+            JobEntity::Artist(id) => format!("artist:{}/", id),
+            JobEntity::Album(id) => {
+                let row = sqlx::query!(r#"SELECT "artist_id" FROM album WHERE "id" = $1"#, id)
+                    .fetch_optional(&self.pool)
+                    .await?;
+                match row {
+                    Some(row) => format!("artist:{}/album:{}/", row.artist_id, id),
+                    None => format!("album:{}/", id),
+                }
+            }
+            JobEntity::Track(id) => {
+                let row = sqlx::query!(
+                    r#"SELECT
+                        al."artist_id",
+                        t."album_id"
+                    FROM track t
+                    INNER JOIN album al ON t."album_id" = al."id"
+                    WHERE t."id" = $1"#,
+                    id
+                )
+                .fetch_optional(&self.pool)
+                .await?;
+                match row {
+                    Some(row) => format!(
+                        "artist:{}/album:{}/track:{}/",
+                        row.artist_id, row.album_id, id
+                    ),
+                    None => format!("track:{}/", id),
+                }
+            }
+            JobEntity::Lyrics(id) => {
+                let row = sqlx::query!(
+                    r#"SELECT
+                        al."artist_id",
+                        t."album_id",
+                        l."track_id"
+                    FROM lyrics l
+                    INNER JOIN track t ON l."track_id" = t."id"
+                    INNER JOIN album al ON t."album_id" = al."id"
+                    WHERE l."id" = $1"#,
+                    id
+                )
+                .fetch_optional(&self.pool)
+                .await?;
+                match row {
+                    Some(row) => format!(
+                        "artist:{}/album:{}/track:{}/",
+                        row.artist_id, row.album_id, row.track_id
+                    ),
+                    None => format!("lyrics:{}/", id),
+                }
+            }
+        };
+        Ok(key)
+    }
+
+    fn dispatch(state: &Arc<AppState>, permit: WorkerPermit, job: Job) {
+        let state = state.clone();
+        tokio::spawn(async move {
+            Self::run_job(&state, job).await;
+            drop(permit);
+            state.job_service.notify.notify_one();
+        });
+    }
+
+    async fn run_job(state: &Arc<AppState>, job: Job) {
+        let cancel = CancellationToken::default();
+        state
+            .job_service
+            .running
+            .lock()
+            .unwrap()
+            .insert(job.id, cancel.clone());
+        Self::run_claimed(state, job.clone(), &cancel).await;
+        state.job_service.running.lock().unwrap().remove(&job.id);
+    }
+
+    async fn run_claimed(state: &Arc<AppState>, mut job: Job, cancel: &CancellationToken) {
+        match state.job_service.claim(job.id).await {
+            Ok(true) => {}
+            Ok(false) => return,
+            Err(e) => {
+                println!("Error while claiming job #{}: {:}", job.id, e);
+                return;
+            }
+        }
+        job.status = JobStatus::Running;
+        state
+            .event_service
+            .send(Event::JobStart { job: job.clone() })
+            .ok();
+        let context = JobContext {
+            job_id: job.id,
+            state: state.clone(),
+            cancel: cancel.clone(),
+            params: (),
+            nested: false,
+        };
+        match job.run(state, cancel).await {
+            Ok(()) => {
+                state.job_service.update_done(job.id).await.ok();
+            }
+            Err(e) if is_cancelled(&e) => {
+                context.warn("Job cancelled");
                 state
                     .job_service
-                    .update_failed(job.id, e.to_string())
+                    .update_finished(job.id, JobStatus::Cancelled)
                     .await
                     .ok();
-            } else {
-                state.job_service.update_done(job.id).await.ok();
             }
-            if let Ok(job) = state.job_service.find(job.id).await {
-                state.event_service.send(Event::JobEnd { job }).ok();
+            Err(e) => {
+                context.error(format!("Job failed: {:}", e));
+                state
+                    .job_service
+                    .update_failed(job.id, e.to_string())
+                    .await
+                    .ok();
+                let policy = job.payload.retry_policy();
+                // A job that failed after being cancelled is recorded, but not run again
+                if !cancel.is_cancelled()
+                    && job.attempt < job.max_attempts
+                    && (policy.retryable)(&e)
+                {
+                    let delay = policy.delay_after(job.attempt);
+                    context.warn(format!(
+                        "Retrying in {}s (attempt {}/{})",
+                        delay,
+                        job.attempt + 1,
+                        job.max_attempts
+                    ));
+                    let retry = CreateJob {
+                        priority: job.priority,
+                        attempt: job.attempt + 1,
+                        max_attempts: job.max_attempts,
+                        retry_of: Some(job.id),
+                        delay: Some(delay),
+                        ..CreateJob::new(job.payload.clone(), job.lane)
+                    };
+                    if let Err(e) = state.job_service.create(&retry).await {
+                        println!("Error while scheduling retry of job #{}: {:}", job.id, e);
+                    }
+                }
             }
         }
+        let settings = state.settings_service.get().await;
+        state.job_log_service.flush().await;
+        if let Err(e) = state
+            .job_log_service
+            .purge(job.id, settings.job_logs.max_per_job)
+            .await
+        {
+            println!("Error while purging job logs: {:}", e);
+        }
+        if let Ok(job) = state.job_service.find(job.id).await {
+            state.event_service.send(Event::JobEnd { job }).ok();
+        }
     }
 
     pub async fn count(&self) -> Result<i64> {
@@ -123,41 +336,152 @@ impl JobService {
         row.try_into()
     }
 
-    pub async fn create(&self, payload: &JobPayload) -> Result<i64> {
-        let payload_string = serde_json::to_string(payload)?;
+    pub async fn create(&self, job: &CreateJob) -> Result<i64> {
+        Self::insert(&self.pool, job).await
+    }
+
+    async fn insert<'c>(
+        executor: impl Executor<'c, Database = Sqlite>,
+        job: &CreateJob,
+    ) -> Result<i64> {
+        let payload_string = serde_json::to_string(&job.payload)?;
         let status_string = JobStatus::Pending.to_string();
+        let lane_string = job.lane.to_string();
+        let delay_modifier = job.delay.map(|delay| format!("+{} seconds", delay));
         let row = sqlx::query_as!(
             IdRow,
             r#"INSERT INTO job (
                 "payload",
-                "status"
+                "status",
+                "lane",
+                "priority",
+                "attempt",
+                "max_attempts",
+                "retry_of",
+                "run_after"
             ) VALUES (
-                $1, $2
+                $1, $2, $3, $4, $5, $6, $7, datetime('now', $8)
             ) RETURNING "id""#,
             payload_string,
             status_string,
+            lane_string,
+            job.priority,
+            job.attempt,
+            job.max_attempts,
+            job.retry_of,
+            delay_modifier,
         )
-        .fetch_one(&self.pool)
+        .fetch_one(executor)
         .await?;
         Ok(row.id)
     }
 
-    pub async fn update_status(&self, id: i64, status: JobStatus) -> Result<()> {
-        let status_string = status.to_string();
+    /// Due pending jobs in the order they should start, at most `limit` per lane.
+    pub async fn find_pending(&self, limit: i64) -> Result<Vec<Job>> {
+        let query = format!(
+            r#"{SELECT}
+            WHERE j."id" IN (
+                SELECT "id" FROM (
+                    SELECT
+                        "id",
+                        ROW_NUMBER() OVER (
+                            PARTITION BY "lane"
+                            ORDER BY "priority" DESC, "id" ASC
+                        ) AS "rank"
+                    FROM job
+                    WHERE "status" = $1
+                        AND ("run_after" IS NULL OR "run_after" <= datetime('now'))
+                )
+                WHERE "rank" <= $2
+            )
+            ORDER BY j."priority" DESC, j."id" ASC"#
+        );
+        let rows: Vec<JobRow> = sqlx::query_as(query.as_str())
+            .bind(JobStatus::Pending.to_string())
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?;
+        rows.into_iter().map(Job::try_from).collect()
+    }
+
+    pub async fn claim(&self, id: i64) -> Result<bool> {
+        let pending_string = JobStatus::Pending.to_string();
+        let running_string = JobStatus::Running.to_string();
+        let result = sqlx::query!(
+            r#"UPDATE job
+            SET
+                "status" = $1,
+                "started_at" = CURRENT_TIMESTAMP
+            WHERE "id" = $2 AND "status" = $3"#,
+            running_string,
+            id,
+            pending_string,
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Fails running jobs left behind by a crash that have no attempts left, so a job that
+    /// takes the process down does not loop on every restart.
+    pub async fn fail_interrupted(&self) -> Result<u64> {
+        let failed_string = JobStatus::Failed.to_string();
+        let running_string = JobStatus::Running.to_string();
+        let result = sqlx::query!(
+            r#"UPDATE job
+            SET
+                "status" = $1,
+                "error" = 'Interrupted by a restart',
+                "completed_at" = CURRENT_TIMESTAMP
+            WHERE "status" = $2 AND "attempt" >= "max_attempts""#,
+            failed_string,
+            running_string,
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Puts running jobs left behind by a crash back in the queue, counting the interrupted run
+    /// as an attempt.
+    pub async fn requeue_running(&self) -> Result<u64> {
+        let pending_string = JobStatus::Pending.to_string();
+        let running_string = JobStatus::Running.to_string();
+        let result = sqlx::query!(
+            r#"UPDATE job
+            SET
+                "status" = $1,
+                "started_at" = NULL,
+                "attempt" = "attempt" + 1
+            WHERE "status" = $2"#,
+            pending_string,
+            running_string,
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn update_progress(&self, id: i64, progress: &JobProgress) -> Result<()> {
+        let progress_string = serde_json::to_string(progress)?;
         sqlx::query!(
             r#"UPDATE job
-            SET "status" = $1
+            SET "progress" = $1
             WHERE "id" = $2"#,
-            status_string,
+            progress_string,
             id,
         )
-        .fetch_one(&self.pool)
+        .execute(&self.pool)
         .await?;
         Ok(())
     }
 
     pub async fn update_done(&self, id: i64) -> Result<()> {
-        let status_string = JobStatus::Done.to_string();
+        self.update_finished(id, JobStatus::Done).await
+    }
+
+    pub async fn update_finished(&self, id: i64, status: JobStatus) -> Result<()> {
+        let status_string = status.to_string();
         sqlx::query!(
             r#"UPDATE job
             SET 
@@ -167,7 +491,7 @@ impl JobService {
             status_string,
             id,
         )
-        .fetch_one(&self.pool)
+        .execute(&self.pool)
         .await?;
         Ok(())
     }
@@ -185,15 +509,385 @@ impl JobService {
             error,
             id,
         )
-        .fetch_one(&self.pool)
+        .execute(&self.pool)
         .await?;
         Ok(())
     }
 
+    pub async fn cancel(&self, id: i64) -> Result<bool> {
+        let cancelled_string = JobStatus::Cancelled.to_string();
+        let pending_string = JobStatus::Pending.to_string();
+        let result = sqlx::query!(
+            r#"UPDATE job
+            SET
+                "status" = $1,
+                "completed_at" = CURRENT_TIMESTAMP
+            WHERE "id" = $2 AND "status" = $3"#,
+            cancelled_string,
+            id,
+            pending_string,
+        )
+        .execute(&self.pool)
+        .await?;
+        if result.rows_affected() > 0 {
+            return Ok(true);
+        }
+        match self.running.lock().unwrap().get(&id) {
+            Some(cancel) => {
+                cancel.cancel();
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    async fn find_equivalent<'c>(
+        executor: impl Executor<'c, Database = Sqlite>,
+        payload: &JobPayload,
+    ) -> Result<Option<Job>> {
+        let payload_string = serde_json::to_string(payload)?;
+        let query = format!(
+            r#"{SELECT}
+            WHERE j."status" IN ($1, $2) AND (
+                j."payload" = $3
+                OR (
+                    j."status" = $1
+                    AND json_extract($3, '$.type') = 'searchTrack'
+                    AND json_extract(j."payload", '$.type') = 'searchAlbum'
+                    AND json_extract(j."payload", '$.albumId') = (
+                        SELECT t."album_id"
+                        FROM track t
+                        WHERE t."id" = json_extract($3, '$.trackId')
+                            AND NOT EXISTS (SELECT 1 FROM lyrics l WHERE l."track_id" = t."id")
+                    )
+                )
+            )
+            ORDER BY j."id" ASC
+            LIMIT 1"#
+        );
+        let row: Option<JobRow> = sqlx::query_as(query.as_str())
+            .bind(JobStatus::Pending.to_string())
+            .bind(JobStatus::Running.to_string())
+            .bind(payload_string)
+            .fetch_optional(executor)
+            .await?;
+        row.map(Job::try_from).transpose()
+    }
+
     pub async fn enqueue(&self, payload: JobPayload) -> Result<Job> {
-        let id = self.create(&payload).await?;
-        let job = self.find(id).await?;
-        self.sender.send(job.clone())?;
-        Ok(job)
+        let lane = payload.lane();
+        self.enqueue_job(&CreateJob::new(payload, lane), true).await
+    }
+
+    pub async fn enqueue_user(&self, payload: JobPayload, query: &CreateJobQuery) -> Result<Job> {
+        let priority = query.priority.unwrap_or(payload.priority() + USER_PRIORITY);
+        self.enqueue_job(
+            &CreateJob {
+                priority,
+                ..CreateJob::new(payload, JobLane::User)
+            },
+            !query.force,
+        )
+        .await
+    }
+
+    pub async fn retry(&self, job: &Job) -> Result<Job> {
+        self.enqueue_job(
+            &CreateJob {
+                priority: job.priority,
+                retry_of: Some(job.id),
+                ..CreateJob::new(job.payload.clone(), job.lane)
+            },
+            true,
+        )
+        .await
+    }
+
+    /// Creates the job unless `deduplicate` is set and an equivalent one is already queued or
+    /// running, in which case that job is raised to at least the requested priority. The lookup
+    /// and the insert share an immediate transaction so that concurrent enqueues cannot both
+    /// miss and insert duplicates.
+    async fn enqueue_job(&self, create: &CreateJob, deduplicate: bool) -> Result<Job> {
+        let mut tx = self.pool.begin_with("BEGIN IMMEDIATE").await?;
+        let existing = if deduplicate {
+            Self::find_equivalent(&mut *tx, &create.payload).await?
+        } else {
+            None
+        };
+        let id = match &existing {
+            Some(job) => {
+                sqlx::query!(
+                    r#"UPDATE job
+                    SET "priority" = MAX("priority", $1)
+                    WHERE "id" = $2"#,
+                    create.priority,
+                    job.id,
+                )
+                .execute(&mut *tx)
+                .await?;
+                job.id
+            }
+            None => Self::insert(&mut *tx, create).await?,
+        };
+        tx.commit().await?;
+
+        if existing.is_none() {
+            self.notify.notify_one();
+        }
+        self.find(id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::fixtures;
+    use crate::worker::jobs::{search_album::SearchAlbumParams, search_track::SearchTrackParams};
+
+    async fn service() -> JobService {
+        JobService::new(fixtures::pool().await)
+    }
+
+    #[tokio::test]
+    async fn test_claim_and_requeue() {
+        let jobs = service().await;
+        let first = jobs.enqueue(JobPayload::SyncLibrary).await.unwrap();
+        let second = jobs
+            .enqueue_user(
+                JobPayload::SearchTrack(SearchTrackParams { track_id: 1 }),
+                &CreateJobQuery::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(first.lane, JobLane::Library);
+        assert_eq!(second.lane, JobLane::User);
+
+        assert!(jobs.claim(first.id).await.unwrap());
+        assert!(!jobs.claim(first.id).await.unwrap());
+        let pending = jobs.find_pending(PENDING_BATCH).await.unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].id, second.id);
+
+        let scan = jobs.enqueue(JobPayload::ScanLibrary).await.unwrap();
+        assert!(jobs.claim(scan.id).await.unwrap());
+
+        assert_eq!(jobs.fail_interrupted().await.unwrap(), 1);
+        assert_eq!(jobs.requeue_running().await.unwrap(), 1);
+        let first = jobs.find(first.id).await.unwrap();
+        assert_eq!(first.status, JobStatus::Pending);
+        assert_eq!(first.attempt, 2);
+        assert_eq!(jobs.find(scan.id).await.unwrap().status, JobStatus::Failed);
+        assert_eq!(jobs.find_pending(PENDING_BATCH).await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_pending_batch() {
+        let jobs = service().await;
+        for track_id in 1..=3 {
+            jobs.enqueue(JobPayload::SearchTrack(SearchTrackParams { track_id }))
+                .await
+                .unwrap();
+        }
+        jobs.enqueue(JobPayload::SyncLibrary).await.unwrap();
+
+        let pending = jobs.find_pending(2).await.unwrap();
+        let lanes: Vec<JobLane> = pending.iter().map(|job| job.lane).collect();
+        assert_eq!(
+            lanes,
+            vec![JobLane::Search, JobLane::Search, JobLane::Library]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cancel() {
+        let jobs = service().await;
+        let pending = jobs.enqueue(JobPayload::SyncLibrary).await.unwrap();
+        assert!(jobs.cancel(pending.id).await.unwrap());
+        assert_eq!(
+            jobs.find(pending.id).await.unwrap().status,
+            JobStatus::Cancelled
+        );
+        assert!(!jobs.claim(pending.id).await.unwrap());
+        assert!(!jobs.cancel(pending.id).await.unwrap());
+
+        let running = jobs.enqueue(JobPayload::SearchLibrary).await.unwrap();
+        let cancel = CancellationToken::default();
+        jobs.running
+            .lock()
+            .unwrap()
+            .insert(running.id, cancel.clone());
+        assert!(jobs.claim(running.id).await.unwrap());
+        assert!(jobs.cancel(running.id).await.unwrap());
+        assert!(cancel.is_cancelled());
+    }
+
+    #[tokio::test]
+    async fn test_retry() {
+        let jobs = service().await;
+        let job = jobs.enqueue(JobPayload::SyncLibrary).await.unwrap();
+        assert_eq!(job.attempt, 1);
+        assert_eq!(job.max_attempts, 3);
+        assert!(jobs.claim(job.id).await.unwrap());
+        jobs.update_failed(job.id, "boom".into()).await.unwrap();
+
+        let delayed = jobs
+            .create(&CreateJob {
+                attempt: 2,
+                retry_of: Some(job.id),
+                delay: Some(60),
+                ..CreateJob::new(job.payload.clone(), job.lane)
+            })
+            .await
+            .unwrap();
+        assert!(jobs.find_pending(PENDING_BATCH).await.unwrap().is_empty());
+        assert_eq!(jobs.retry(&job).await.unwrap().id, delayed);
+
+        jobs.cancel(delayed).await.unwrap();
+        let retried = jobs.retry(&job).await.unwrap();
+        assert_eq!(retried.attempt, 1);
+        assert_eq!(retried.retry_of, Some(job.id));
+        let pending = jobs.find_pending(PENDING_BATCH).await.unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].id, retried.id);
+    }
+
+    #[tokio::test]
+    async fn test_deduplicate() {
+        let jobs = service().await;
+        let sync = jobs.enqueue(JobPayload::SyncLibrary).await.unwrap();
+        assert_eq!(
+            jobs.enqueue(JobPayload::SyncLibrary).await.unwrap().id,
+            sync.id
+        );
+        assert!(jobs.claim(sync.id).await.unwrap());
+        assert_eq!(
+            jobs.enqueue(JobPayload::SyncLibrary).await.unwrap().id,
+            sync.id
+        );
+        let forced = jobs
+            .enqueue_user(
+                JobPayload::SyncLibrary,
+                &CreateJobQuery {
+                    force: true,
+                    priority: None,
+                },
+            )
+            .await
+            .unwrap();
+        assert_ne!(forced.id, sync.id);
+
+        let album = jobs
+            .enqueue(JobPayload::SearchAlbum(SearchAlbumParams { album_id: 1 }))
+            .await
+            .unwrap();
+        let search_track = |track_id| JobPayload::SearchTrack(SearchTrackParams { track_id });
+        assert_eq!(jobs.enqueue(search_track(1)).await.unwrap().id, album.id);
+        assert_ne!(jobs.enqueue(search_track(2)).await.unwrap().id, album.id);
+        assert_ne!(jobs.enqueue(search_track(3)).await.unwrap().id, album.id);
+    }
+
+    #[tokio::test]
+    async fn test_priority() {
+        let jobs = service().await;
+        let library = jobs.enqueue(JobPayload::SearchLibrary).await.unwrap();
+        let album = jobs
+            .enqueue(JobPayload::SearchAlbum(SearchAlbumParams { album_id: 2 }))
+            .await
+            .unwrap();
+        let user = jobs
+            .enqueue_user(JobPayload::SyncLibrary, &CreateJobQuery::default())
+            .await
+            .unwrap();
+        let overridden = jobs
+            .enqueue_user(
+                JobPayload::ScanLibrary,
+                &CreateJobQuery {
+                    force: false,
+                    priority: Some(-1),
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(user.priority, USER_PRIORITY);
+
+        let pending: Vec<i64> = jobs
+            .find_pending(PENDING_BATCH)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|job| job.id)
+            .collect();
+        assert_eq!(pending, vec![user.id, album.id, library.id, overridden.id]);
+    }
+
+    #[tokio::test]
+    async fn test_progress() {
+        let jobs = service().await;
+        let job = jobs.enqueue(JobPayload::SyncLibrary).await.unwrap();
+        assert_eq!(job.progress, None);
+
+        let progress = JobProgress::new(3, 120, "Syncing artist").with_entity("Björk");
+        jobs.update_progress(job.id, &progress).await.unwrap();
+        assert_eq!(
+            jobs.find(job.id).await.unwrap().progress,
+            Some(progress.clone())
+        );
+        assert_eq!(progress.to_string(), "[3/120] Syncing artist: Björk");
+    }
+
+    #[tokio::test]
+    async fn test_lock_key() {
+        let jobs = service().await;
+        assert_eq!(jobs.lock_key(JobEntity::Library).await.unwrap(), "library/");
+        assert_eq!(
+            jobs.lock_key(JobEntity::Album(2)).await.unwrap(),
+            "artist:1/album:2/"
+        );
+        assert_eq!(
+            jobs.lock_key(JobEntity::Track(3)).await.unwrap(),
+            "artist:1/album:2/track:3/"
+        );
+        assert_eq!(
+            jobs.lock_key(JobEntity::Lyrics(1)).await.unwrap(),
+            "artist:1/album:1/track:2/"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_deduplicate() {
+        let jobs = service().await;
+        let payload = || JobPayload::SearchTrack(SearchTrackParams { track_id: 1 });
+        let (first, second, third) = tokio::join!(
+            jobs.enqueue(payload()),
+            jobs.enqueue(payload()),
+            jobs.enqueue(payload()),
+        );
+        let id = first.unwrap().id;
+        assert_eq!(second.unwrap().id, id);
+        assert_eq!(third.unwrap().id, id);
+    }
+
+    #[tokio::test]
+    async fn test_deduplicate_raises_priority() {
+        let jobs = service().await;
+        let album = jobs
+            .enqueue(JobPayload::SearchAlbum(SearchAlbumParams { album_id: 1 }))
+            .await
+            .unwrap();
+        let user = jobs
+            .enqueue_user(
+                JobPayload::SearchTrack(SearchTrackParams { track_id: 1 }),
+                &CreateJobQuery::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(user.id, album.id);
+        assert_eq!(user.priority, 30 + USER_PRIORITY);
+
+        let lower = jobs
+            .enqueue(JobPayload::SearchAlbum(SearchAlbumParams { album_id: 1 }))
+            .await
+            .unwrap();
+        assert_eq!(lower.priority, 30 + USER_PRIORITY);
     }
 }
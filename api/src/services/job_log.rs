@@ -0,0 +1,250 @@
+use anyhow::Result;
+use sqlx::{prelude::FromRow, Pool, Sqlite};
+use tokio::sync::{
+    mpsc::{self, UnboundedReceiver, UnboundedSender},
+    oneshot, Mutex,
+};
+
+use crate::models::{
+    generic::{IdRow, Page, Pageable, TotalRow},
+    job_log::{CreateJobLog, JobLog},
+};
+
+#[derive(FromRow)]
+struct JobLogRow {
+    id: i64,
+    created_at: String,
+    job_id: i64,
+    level: String,
+    message: String,
+}
+
+impl TryFrom<JobLogRow> for JobLog {
+    type Error = anyhow::Error;
+
+    fn try_from(value: JobLogRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: value.id,
+            created_at: value.created_at,
+            job_id: value.job_id,
+            level: value.level.parse()?,
+            message: value.message,
+        })
+    }
+}
+
+enum JobLogMessage {
+    Log(CreateJobLog),
+    Flush(oneshot::Sender<()>),
+}
+
+pub struct JobLogService {
+    pool: Pool<Sqlite>,
+    sender: UnboundedSender<JobLogMessage>,
+    receiver: Mutex<Option<UnboundedReceiver<JobLogMessage>>>,
+}
+
+impl JobLogService {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        Self {
+            pool,
+            sender,
+            receiver: Mutex::new(Some(receiver)),
+        }
+    }
+
+    pub fn push(&self, log: CreateJobLog) {
+        self.sender.send(JobLogMessage::Log(log)).ok();
+    }
+
+    /// Waits until every log pushed so far has been written.
+    pub async fn flush(&self) {
+        if self.receiver.lock().await.is_some() {
+            // The worker isn't running, nothing would answer.
+            return;
+        }
+        let (sender, receiver) = oneshot::channel();
+        if self.sender.send(JobLogMessage::Flush(sender)).is_ok() {
+            receiver.await.ok();
+        }
+    }
+
+    pub async fn start_worker(&self) {
+        let Some(mut receiver) = self.receiver.lock().await.take() else {
+            return;
+        };
+        while let Some(message) = receiver.recv().await {
+            match message {
+                JobLogMessage::Log(log) => {
+                    if let Err(e) = self.create(&log).await {
+                        println!("Failed to save job log: {:?}", e);
+                    }
+                }
+                JobLogMessage::Flush(sender) => {
+                    sender.send(()).ok();
+                }
+            }
+        }
+    }
+
+    pub async fn create(&self, log: &CreateJobLog) -> Result<i64> {
+        let level = log.level.to_string();
+        let row = sqlx::query_as!(
+            IdRow,
+            r#"INSERT INTO job_log (
+                "job_id",
+                "level",
+                "message"
+            ) VALUES (
+                $1, $2, $3
+            ) RETURNING "id""#,
+            log.job_id,
+            level,
+            log.message,
+        )
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(row.id)
+    }
+
+    pub async fn find_page(&self, job_id: i64, pageable: &Pageable) -> Result<Page<JobLog>> {
+        let total = sqlx::query_as!(
+            TotalRow,
+            r#"SELECT COUNT(*) as "total" FROM job_log WHERE "job_id" = $1"#,
+            job_id
+        )
+        .fetch_one(&self.pool)
+        .await?
+        .total;
+
+        let mut qb = sqlx::QueryBuilder::new(
+            r#"SELECT
+                l."id",
+                l."created_at",
+                l."job_id",
+                l."level",
+                l."message"
+            FROM job_log l
+            WHERE l."job_id" = "#,
+        );
+        qb.push_bind(job_id);
+        qb.push(r#" ORDER BY l."id" ASC"#);
+        pageable.push_limit_offset(&mut qb);
+        let rows: Vec<JobLogRow> = qb.build_query_as().fetch_all(&self.pool).await?;
+        Ok(Page {
+            total,
+            items: rows
+                .into_iter()
+                .map(JobLog::try_from)
+                .collect::<Result<Vec<JobLog>>>()?,
+        })
+    }
+
+    pub async fn purge_expired(&self, retention_days: u64) -> Result<u64> {
+        let retention_modifier = format!("-{} days", retention_days);
+        let result = sqlx::query!(
+            r#"DELETE FROM job_log
+            WHERE "created_at" <= datetime('now', $1)"#,
+            retention_modifier,
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn purge(&self, job_id: i64, max_per_job: u32) -> Result<u64> {
+        let max_per_job = max_per_job as i64;
+        let result = sqlx::query!(
+            r#"DELETE FROM job_log
+            WHERE "job_id" = $1 AND "id" NOT IN (
+                SELECT "id" FROM job_log
+                WHERE "job_id" = $1
+                ORDER BY "id" DESC
+                LIMIT $2
+            )"#,
+            job_id,
+            max_per_job,
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::job_log::JobLogLevel;
+    use crate::utils::fixtures;
+
+    async fn service() -> JobLogService {
+        JobLogService::new(fixtures::pool().await)
+    }
+
+    async fn insert_jobs(logs: &JobLogService) {
+        sqlx::raw_sql(
+            r#"INSERT INTO job ("id", "payload", "status") VALUES
+                (1, '{"type":"syncLibrary"}', 'done'),
+                (2, '{"type":"scanLibrary"}', 'done');"#,
+        )
+        .execute(&logs.pool)
+        .await
+        .unwrap();
+    }
+
+    fn log(job_id: i64, message: &str) -> CreateJobLog {
+        CreateJobLog {
+            job_id,
+            level: JobLogLevel::Info,
+            message: message.into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_purge() {
+        let logs = service().await;
+        insert_jobs(&logs).await;
+        for i in 0..5 {
+            logs.create(&log(1, &format!("line {}", i))).await.unwrap();
+        }
+        logs.create(&log(2, "other")).await.unwrap();
+        sqlx::query(
+            r#"UPDATE job_log SET "created_at" = datetime('now', '-30 days') WHERE "job_id" = 2"#,
+        )
+        .execute(&logs.pool)
+        .await
+        .unwrap();
+
+        assert_eq!(logs.purge(1, 3).await.unwrap(), 2);
+        assert_eq!(logs.purge_expired(14).await.unwrap(), 1);
+
+        let page = logs.find_page(1, &Pageable::default()).await.unwrap();
+        assert_eq!(page.total, 3);
+        let messages: Vec<&str> = page.items.iter().map(|l| l.message.as_str()).collect();
+        assert_eq!(messages, vec!["line 2", "line 3", "line 4"]);
+        assert_eq!(
+            logs.find_page(2, &Pageable::default()).await.unwrap().total,
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn test_flush() {
+        let logs = std::sync::Arc::new(service().await);
+        insert_jobs(&logs).await;
+        {
+            let logs = logs.clone();
+            tokio::spawn(async move { logs.start_worker().await });
+        }
+        tokio::task::yield_now().await;
+        for i in 0..3 {
+            logs.push(log(1, &format!("line {}", i)));
+        }
+        logs.flush().await;
+        assert_eq!(
+            logs.find_page(1, &Pageable::default()).await.unwrap().total,
+            3
+        );
+    }
+}
@@ -44,7 +44,12 @@ impl LidarrService {
         if let Some(api_key) = settings.lidarr.api_key {
             builder = builder.header("X-Api-Key", api_key)
         }
-        Ok(builder.send().await?.json::<T>().await?)
+        Ok(builder
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<T>()
+            .await?)
     }
 
     pub async fn list_artists(
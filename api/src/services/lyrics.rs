@@ -26,13 +26,20 @@ struct LyricsRow {
     pub file_path: String,
     pub checksum: String,
     pub provider: Option<String>,
+    pub provider_identifier: Option<String>,
+    pub published_at: Option<String>,
+    pub publish_error: Option<String>,
 
     pub track_id: i64,
     pub track_track_number: Option<i64>,
     pub track_title: String,
     pub track_file_path: String,
     pub track_duration_ms: Option<i64>,
+    pub track_musicbrainz_id: Option<String>,
+    pub track_recording_musicbrainz_id: Option<String>,
+    pub track_isrc: Option<String>,
     pub track_has_lyrics: bool,
+    pub track_lyrics_pinned: bool,
 
     pub track_artist_id: i64,
     pub track_artist_name: String,
@@ -64,13 +71,20 @@ impl From<LyricsRow> for Lyrics {
             file_path: value.file_path,
             checksum: value.checksum,
             provider: value.provider,
+            provider_identifier: value.provider_identifier,
+            published_at: value.published_at,
+            publish_error: value.publish_error,
             track: Track {
                 id: value.track_id,
                 track_number: value.track_track_number.unwrap_or(0),
                 title: value.track_title,
                 file_path: value.track_file_path,
                 duration_ms: value.track_duration_ms.unwrap_or(0),
+                musicbrainz_id: value.track_musicbrainz_id,
+                recording_musicbrainz_id: value.track_recording_musicbrainz_id,
+                isrc: value.track_isrc,
                 has_lyrics: value.track_has_lyrics,
+                lyrics_pinned: value.track_lyrics_pinned,
                 artist: Artist {
                     id: value.track_artist_id,
                     name: value.track_artist_name,
@@ -113,13 +127,20 @@ const SELECT: &str = r#"SELECT
     l."file_path", 
     l."checksum",
     l."provider",
+    l."provider_identifier",
+    l."published_at",
+    l."publish_error",
 
     t."id" as "track_id",
     t."track_number" as "track_track_number",
     t."title" as "track_title",
     t."file_path" as "track_file_path",
     t."duration_ms" as "track_duration_ms",
+    t."musicbrainz_id" as "track_musicbrainz_id",
+    t."recording_musicbrainz_id" as "track_recording_musicbrainz_id",
+    t."isrc" as "track_isrc",
     EXISTS (SELECT 1 FROM lyrics l WHERE l."track_id" = t."id") AS "track_has_lyrics",
+    t."lyrics_pinned" as "track_lyrics_pinned",
 
     ar."id" as "track_artist_id",
     ar."name" as "track_artist_name",
@@ -284,15 +305,17 @@ impl LyricsService {
             r#"INSERT INTO lyrics (
                 "language", 
                 "provider", 
+                "provider_identifier", 
                 "synced", 
                 "file_path", 
                 "checksum", 
                 "track_id"
             ) VALUES (
-                $1, $2, $3, $4, $5, $6
+                $1, $2, $3, $4, $5, $6, $7
             ) RETURNING "id""#,
             data.language,
             data.provider,
+            data.provider_identifier,
             data.synced,
             data.file_path,
             data.checksum,
@@ -305,6 +328,21 @@ impl LyricsService {
         Ok(row.id)
     }
 
+    pub async fn set_published(&self, id: i64, error: &Option<String>) -> Result<()> {
+        sqlx::query!(
+            r#"UPDATE lyrics
+            SET
+                "published_at" = CASE WHEN $1 IS NULL THEN CURRENT_TIMESTAMP ELSE "published_at" END,
+                "publish_error" = $1
+            WHERE "id" = $2"#,
+            error,
+            id,
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
     pub async fn remove(&self, id: i64) -> Result<()> {
         let path = self.resolve_path(id).await?;
         let lyrics = self.find(id).await?;
@@ -359,6 +397,7 @@ impl LyricsService {
             synced: lrc.lyrics_type() == LyricsType::Synced,
             language: None,
             provider: None,
+            provider_identifier: None,
         };
         Ok(self.create(&data).await?)
     }
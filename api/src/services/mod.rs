@@ -1,8 +1,11 @@
 pub mod album;
 pub mod artist;
+pub mod blocklist;
+pub mod cache;
 pub mod event;
 pub mod image;
 pub mod job;
+pub mod job_log;
 pub mod jwt;
 pub mod lidarr;
 pub mod lyrics;
@@ -13,3 +16,4 @@ pub mod search;
 pub mod settings;
 pub mod stats;
 pub mod track;
+pub mod track_search;
@@ -1,16 +1,40 @@
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
 use anyhow::{anyhow, Result};
-use futures::{stream::FuturesUnordered, TryStreamExt};
+use futures::{stream::FuturesUnordered, StreamExt};
 
 use crate::{
     models::{
-        provider::{Provider, ProviderFile, ProviderMetadata, ProviderResult},
+        provider::{
+            Provider, ProviderError, ProviderFile, ProviderMetadata, ProviderResult,
+            ProviderResults, ProviderStatus, ScoreBreakdown,
+        },
+        settings::{LyricsSettings, NormalizationSettings, Settings},
         track::Track,
     },
-    worker::{provider::LyricsProvider, providers::lrclib::LrcLibProvider},
+    services::{cache::CacheService, settings::SettingsService},
+    utils::{checksum::md5sum, normalize::normalize},
+    worker::{
+        guard::{with_timeout, ProviderGuard},
+        provider::LyricsProvider,
+        providers::{
+            genius::GeniusProvider, local::LocalProvider, lrclib::LrcLibProvider,
+            musixmatch::MusixmatchProvider, netease::NeteaseProvider, script::ScriptProvider,
+            template::TemplateProvider,
+        },
+    },
 };
 
-fn score_strings(a: &String, b: &String) -> f64 {
-    strsim::jaro_winkler(a, b)
+/// Translations stay available for manual import but should never win auto-import over
+/// the original lyrics.
+const TRANSLATION_PENALTY: f64 = 0.5;
+
+fn score_strings(a: &str, b: &str, normalization: &NormalizationSettings) -> f64 {
+    strsim::jaro_winkler(&normalize(a, normalization), &normalize(b, normalization))
+}
+
+fn provider_timeout(settings: &Settings) -> Duration {
+    Duration::from_secs(settings.lyrics.provider_timeout.max(1))
 }
 
 fn score_durations(a: i64, b: i64) -> f64 {
@@ -18,80 +42,455 @@ fn score_durations(a: i64, b: i64) -> f64 {
     (1.0 - (diff as f64 / 10_000.0)).clamp(0.0, 1.0)
 }
 
+fn same_identifier(a: &Option<String>, b: &Option<String>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => !a.trim().is_empty() && a.trim().eq_ignore_ascii_case(b.trim()),
+        _ => false,
+    }
+}
+
+fn normalize_key(value: &str) -> String {
+    value
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+pub fn search_cache_key(track: &Track, scope: &str) -> String {
+    let key = format!(
+        "search:{}|{}|{}|{}|{}|{}|{}|{}",
+        normalize_key(&track.title),
+        normalize_key(&track.artist.name),
+        normalize_key(&track.album.album.title),
+        (track.duration_ms as f64 / 1_000.0).round() as i64,
+        track
+            .recording_musicbrainz_id
+            .as_deref()
+            .unwrap_or_default(),
+        track.isrc.as_deref().unwrap_or_default(),
+        track.artist.musicbrainz_id.as_deref().unwrap_or_default(),
+        track
+            .album
+            .album
+            .musicbrainz_id
+            .as_deref()
+            .unwrap_or_default()
+    );
+    if scope.is_empty() {
+        key
+    } else {
+        format!("{}|{}", key, md5sum(&scope.to_string()))
+    }
+}
+
+pub fn download_cache_key(file: &ProviderFile) -> String {
+    format!("download:{}", file.identifier)
+}
+
 pub struct ProviderService {
+    settings_service: Arc<SettingsService>,
+    cache_service: Arc<CacheService>,
     providers: Vec<Provider>,
+    guards: HashMap<&'static str, ProviderGuard>,
 }
 
 impl ProviderService {
-    pub fn new() -> Self {
+    pub fn new(settings_service: Arc<SettingsService>, cache_service: Arc<CacheService>) -> Self {
+        let providers = vec![
+            Provider::Local(LocalProvider::new(settings_service.clone())),
+            Provider::LrcLib(LrcLibProvider::new()),
+            Provider::Genius(GeniusProvider::new(settings_service.clone())),
+            Provider::Musixmatch(MusixmatchProvider::new(settings_service.clone())),
+            Provider::Netease(NeteaseProvider::new()),
+            Provider::Script(ScriptProvider::new(settings_service.clone())),
+            Provider::Template(TemplateProvider::new(settings_service.clone())),
+        ];
+        let guards = providers
+            .iter()
+            .map(|provider| (provider.name(), ProviderGuard::new(provider.name())))
+            .collect();
         Self {
-            providers: vec![Provider::LrcLib(LrcLibProvider::new())],
+            settings_service,
+            cache_service,
+            providers,
+            guards,
         }
     }
 
-    pub fn score(track: &Track, file: &ProviderFile) -> f64 {
-        let score_track_name = score_strings(&track.title, &file.track_name);
-        let score_artist_name = score_strings(&track.artist.name, &file.artist_name);
-        let score_album_title = score_strings(&track.album.album.title, &file.album_title);
-        let score_duration = match file.duration_ms {
-            Some(duration_ms) => score_durations(track.duration_ms, duration_ms),
-            None => 0.5,
-        };
+    pub fn statuses(&self) -> Vec<ProviderStatus> {
+        self.providers
+            .iter()
+            .filter_map(|provider| self.guards.get(provider.name()))
+            .map(ProviderGuard::status)
+            .collect()
+    }
+
+    async fn search_provider(
+        &self,
+        track: &Track,
+        provider: &Provider,
+        settings: &Settings,
+    ) -> Result<Vec<ProviderFile>> {
+        let timeout = provider_timeout(settings);
+        match self.guards.get(provider.name()) {
+            Some(guard) if provider.remote() => {
+                guard
+                    .run(&settings.providers.limits, timeout, || {
+                        provider.search_lyrics(track)
+                    })
+                    .await
+            }
+            _ => with_timeout(timeout, provider.search_lyrics(track)).await,
+        }
+    }
+
+    async fn download_provider(
+        &self,
+        file: &ProviderFile,
+        provider: &Provider,
+        settings: &Settings,
+    ) -> Result<String> {
+        let timeout = provider_timeout(settings);
+        match self.guards.get(provider.name()) {
+            Some(guard) if provider.remote() => {
+                guard
+                    .run(&settings.providers.limits, timeout, || {
+                        provider.download(file)
+                    })
+                    .await
+            }
+            _ => with_timeout(timeout, provider.download(file)).await,
+        }
+    }
+
+    pub fn score_breakdown(
+        track: &Track,
+        file: &ProviderFile,
+        settings: &LyricsSettings,
+    ) -> ScoreBreakdown {
+        let normalization = &settings.normalization;
+        ScoreBreakdown {
+            track_name: score_strings(&track.title, &file.track_name, normalization),
+            artist_name: score_strings(&track.artist.name, &file.artist_name, normalization),
+            album_title: score_strings(&track.album.album.title, &file.album_title, normalization),
+            duration: match file.duration_ms {
+                Some(duration_ms) => score_durations(track.duration_ms, duration_ms),
+                None => 0.5,
+            },
+            identifier: same_identifier(&track.isrc, &file.isrc)
+                || same_identifier(&track.recording_musicbrainz_id, &file.musicbrainz_id),
+        }
+    }
+
+    pub fn score(
+        file: &ProviderFile,
+        breakdown: &ScoreBreakdown,
+        settings: &LyricsSettings,
+    ) -> f64 {
+        let score = Self::match_score(file, breakdown, settings);
+        if file.translation {
+            score * TRANSLATION_PENALTY
+        } else {
+            score
+        }
+    }
+
+    fn match_score(
+        file: &ProviderFile,
+        breakdown: &ScoreBreakdown,
+        settings: &LyricsSettings,
+    ) -> f64 {
+        if file.exact || breakdown.identifier {
+            return 1.0;
+        }
+
+        let weights = &settings.weights;
+        let total =
+            weights.track_name + weights.artist_name + weights.album_title + weights.duration;
+        if total <= 0.0 {
+            return 0.0;
+        }
+
+        (breakdown.track_name * weights.track_name
+            + breakdown.artist_name * weights.artist_name
+            + breakdown.album_title * weights.album_title
+            + breakdown.duration * weights.duration)
+            / total
+    }
+
+    pub fn result(
+        track: &Track,
+        provider: &Provider,
+        file: ProviderFile,
+        settings: &LyricsSettings,
+    ) -> ProviderResult {
+        let breakdown = Self::score_breakdown(track, &file, settings);
+        ProviderResult {
+            provider: ProviderMetadata {
+                name: provider.name().into(),
+            },
+            score: Self::score(&file, &breakdown, settings),
+            breakdown,
+            file,
+        }
+    }
+
+    async fn search_files(
+        &self,
+        track: &Track,
+        provider: &Provider,
+        settings: &Settings,
+    ) -> Result<Vec<ProviderFile>> {
+        if !provider.enabled(&settings.providers) {
+            return Ok(Vec::new());
+        }
 
-        score_duration * 0.4
-            + score_track_name * 0.2
-            + score_artist_name * 0.2
-            + score_album_title * 0.2
+        let cache = &settings.cache;
+        if !cache.enabled || !provider.remote() {
+            return self.search_provider(track, provider, settings).await;
+        }
+
+        let key = search_cache_key(track, &provider.cache_scope(&settings.providers));
+        if let Some(files) = self
+            .cache_service
+            .get::<Vec<ProviderFile>>(provider.name(), &key)
+            .await
+            .ok()
+            .flatten()
+        {
+            return Ok(files);
+        }
+
+        let files = self.search_provider(track, provider, settings).await?;
+        let ttl = if files.is_empty() {
+            cache.miss_ttl
+        } else {
+            cache.hit_ttl
+        };
+        self.cache_service
+            .set(provider.name(), &key, &files, ttl)
+            .await
+            .ok();
+        Ok(files)
     }
 
     pub async fn get_provider_results(
         &self,
         track: &Track,
         provider: &Provider,
+        settings: &Settings,
     ) -> Result<Vec<ProviderResult>> {
-        let mut results = provider
-            .search_lyrics(track)
-            .await?
+        let files = self.search_files(track, provider, settings).await?;
+        let mut results = files
             .into_iter()
-            .map(|file| ProviderResult {
-                provider: ProviderMetadata {
-                    name: provider.name().into(),
-                },
-                score: Self::score(&track, &file),
-                file,
-            })
+            .map(|file| Self::result(track, provider, file, &settings.lyrics))
             .collect::<Vec<ProviderResult>>();
         results.sort_by(|a, b| b.score.total_cmp(&a.score));
         Ok(results)
     }
 
-    pub async fn get_results(&self, track: &Track) -> Result<Vec<ProviderResult>> {
+    pub async fn get_results(&self, track: &Track) -> ProviderResults {
+        let settings = self.settings_service.get().await;
+        let settings = &settings;
+
         let mut futures = FuturesUnordered::new();
 
-        for provider in &self.providers {
-            futures.push(self.get_provider_results(track, &provider));
+        for provider in self
+            .providers
+            .iter()
+            .filter(|provider| provider.enabled(&settings.providers))
+        {
+            futures.push(async move {
+                (
+                    provider,
+                    self.get_provider_results(track, provider, settings).await,
+                )
+            });
         }
 
-        let mut results = Vec::<ProviderResult>::new();
+        let mut results = ProviderResults::default();
 
-        while let Some(files) = futures.try_next().await? {
-            for result in files.into_iter() {
-                results.push(result);
+        while let Some((provider, provider_results)) = futures.next().await {
+            results.providers.push(ProviderMetadata {
+                name: provider.name().into(),
+            });
+            match provider_results {
+                Ok(provider_results) => results.results.extend(provider_results),
+                Err(e) => results.errors.push(ProviderError {
+                    provider: ProviderMetadata {
+                        name: provider.name().into(),
+                    },
+                    error: e.to_string(),
+                }),
             }
         }
 
-        Ok(results)
+        results
+    }
+
+    fn find_provider(&self, provider_name: &str) -> Result<&Provider> {
+        self.providers
+            .iter()
+            .find(|provider| provider.name() == provider_name)
+            .ok_or(anyhow!("Provider not found"))
+    }
+
+    pub async fn find_result(
+        &self,
+        track: &Track,
+        provider_name: &str,
+        identifier: &str,
+    ) -> Result<ProviderResult> {
+        let provider = self.find_provider(provider_name)?;
+        let settings = self.settings_service.get().await;
+        self.get_provider_results(track, provider, &settings)
+            .await?
+            .into_iter()
+            .find(|result| result.file.identifier == identifier)
+            .ok_or(anyhow!("Result not found"))
     }
 
     pub async fn download(&self, result: &ProviderResult) -> Result<String> {
-        if let Some(provider) = self
+        let Some(provider) = self
             .providers
             .iter()
             .find(|provider| provider.name() == result.provider.name.as_str())
+        else {
+            return Err(anyhow!("Provider not found"));
+        };
+
+        let settings = self.settings_service.get().await;
+        let cache = &settings.cache;
+        if !cache.enabled || !provider.remote() {
+            return self
+                .download_provider(&result.file, provider, &settings)
+                .await;
+        }
+
+        let key = download_cache_key(&result.file);
+        if let Some(content) = self
+            .cache_service
+            .get::<String>(provider.name(), &key)
+            .await
+            .ok()
+            .flatten()
         {
-            Ok(provider.download(&result.file).await?)
-        } else {
-            Err(anyhow!("Provider not found"))
+            return Ok(content);
+        }
+
+        let content = self
+            .download_provider(&result.file, provider, &settings)
+            .await?;
+        self.cache_service
+            .set(provider.name(), &key, &content, cache.hit_ttl)
+            .await
+            .ok();
+        Ok(content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{models::settings::ScoreWeights, utils::fixtures};
+
+    fn track() -> Track {
+        fixtures::track("Almost Home", "Harbour Lights")
+    }
+
+    fn file(track_name: &str, album_title: &str) -> ProviderFile {
+        ProviderFile {
+            identifier: "1".into(),
+            name: track_name.into(),
+            track_name: track_name.into(),
+            artist_name: "Harbour Lights feat. Someone".into(),
+            album_title: album_title.into(),
+            synced: true,
+            exact: false,
+            translation: false,
+            duration_ms: Some(143_000),
+            isrc: None,
+            musicbrainz_id: None,
+            content: None,
         }
     }
+
+    #[test]
+    fn test_score_normalized() {
+        let settings = LyricsSettings::default();
+        let file = file(
+            "Almost Home (Remastered 2011)",
+            "Tidewater (Deluxe Edition)",
+        );
+        let breakdown = ProviderService::score_breakdown(&track(), &file, &settings);
+        assert_eq!(
+            breakdown,
+            ScoreBreakdown {
+                track_name: 1.0,
+                artist_name: 1.0,
+                album_title: 1.0,
+                duration: 1.0,
+                identifier: false,
+            }
+        );
+        assert_eq!(ProviderService::score(&file, &breakdown, &settings), 1.0);
+    }
+
+    #[test]
+    fn test_score_weights() {
+        let settings = LyricsSettings {
+            weights: ScoreWeights {
+                track_name: 1.0,
+                artist_name: 0.0,
+                album_title: 1.0,
+                duration: 0.0,
+            },
+            ..LyricsSettings::default()
+        };
+        let file = file("Almost Home", "Something Else Entirely");
+        let breakdown = ProviderService::score_breakdown(&track(), &file, &settings);
+        let score = ProviderService::score(&file, &breakdown, &settings);
+        assert_eq!(score, (1.0 + breakdown.album_title) / 2.0);
+        assert!(score < 0.8);
+    }
+
+    #[test]
+    fn test_score_identifier() {
+        let settings = LyricsSettings::default();
+        let mut track = track();
+        track.isrc = Some("GBAYE1100001".into());
+        let mut file = file("Something Else", "Entirely");
+        file.isrc = Some("gbaye1100001".into());
+        let breakdown = ProviderService::score_breakdown(&track, &file, &settings);
+        assert!(breakdown.identifier);
+        assert_eq!(ProviderService::score(&file, &breakdown, &settings), 1.0);
+
+        file.isrc = Some("GBAYE1100002".into());
+        let breakdown = ProviderService::score_breakdown(&track, &file, &settings);
+        assert!(!breakdown.identifier);
+        assert!(ProviderService::score(&file, &breakdown, &settings) < 1.0);
+    }
+
+    #[test]
+    fn test_score_translation() {
+        let settings = LyricsSettings::default();
+        let mut file = file("Almost Home", "Tidewater");
+        let breakdown = ProviderService::score_breakdown(&track(), &file, &settings);
+        let original = ProviderService::score(&file, &breakdown, &settings);
+        file.translation = true;
+        assert!(ProviderService::score(&file, &breakdown, &settings) < original);
+    }
+
+    #[test]
+    fn test_search_cache_key_scope() {
+        let track = track();
+        assert_ne!(
+            search_cache_key(&track, "enhanced=true"),
+            search_cache_key(&track, "enhanced=false")
+        );
+        assert!(
+            search_cache_key(&track, "enhanced=true").starts_with(&search_cache_key(&track, ""))
+        );
+    }
 }
@@ -4,7 +4,10 @@ use anyhow::Result;
 use serde::Serialize;
 use tokio_cron_scheduler::{Job, JobScheduler};
 
-use crate::{models::job::JobPayload, services::job::JobService};
+use crate::{
+    models::job::JobPayload,
+    services::{job::JobService, job_log::JobLogService, settings::SettingsService},
+};
 
 #[derive(Clone, Serialize)]
 pub struct ScheduledJob {
@@ -64,12 +67,36 @@ impl SchedulerService {
             .await?;
         self.add_task("0 30 4 * * *", JobPayload::ScanLibrary)
             .await?;
-        self.add_task("0 30 */6 * * *", JobPayload::SearchLibrary)
+        self.add_task("0 30 * * * *", JobPayload::SearchWanted)
             .await?;
 
         Ok(())
     }
 
+    /// Drops job logs older than the configured retention. Not listed with the tasks since
+    /// it doesn't go through the job queue.
+    pub async fn add_log_retention(
+        &mut self,
+        cron: &str,
+        job_log_service: Arc<JobLogService>,
+        settings_service: Arc<SettingsService>,
+    ) -> Result<uuid::Uuid> {
+        let job = Job::new_async(cron, move |_uuid, _lock| {
+            let job_log_service = job_log_service.clone();
+            let settings_service = settings_service.clone();
+            Box::pin(async move {
+                let settings = settings_service.get().await;
+                if let Err(e) = job_log_service
+                    .purge_expired(settings.job_logs.retention_days)
+                    .await
+                {
+                    tracing::error!(error = %e, "Failed to purge job logs");
+                }
+            })
+        })?;
+        Ok(self.scheduler.add(job).await?)
+    }
+
     pub fn iter_tasks(&'_ self) -> Iter<'_, ScheduledJob> {
         self.tasks.iter()
     }
@@ -16,7 +16,11 @@ struct TrackRow {
     pub title: String,
     pub file_path: String,
     pub duration_ms: Option<i64>,
+    pub musicbrainz_id: Option<String>,
+    pub recording_musicbrainz_id: Option<String>,
+    pub isrc: Option<String>,
     pub has_lyrics: bool,
+    pub lyrics_pinned: bool,
 
     pub artist_id: i64,
     pub artist_name: String,
@@ -48,7 +52,11 @@ impl From<TrackRow> for Track {
             title: value.title,
             file_path: value.file_path,
             duration_ms: value.duration_ms.unwrap_or(0),
+            musicbrainz_id: value.musicbrainz_id,
+            recording_musicbrainz_id: value.recording_musicbrainz_id,
+            isrc: value.isrc,
             has_lyrics: value.has_lyrics,
+            lyrics_pinned: value.lyrics_pinned,
             artist: Artist {
                 id: value.artist_id,
                 name: value.artist_name,
@@ -85,7 +93,11 @@ const SELECT: &str = r#"SELECT
     t."title",
     t."file_path",
     t."duration_ms",
+    t."musicbrainz_id",
+    t."recording_musicbrainz_id",
+    t."isrc",
     EXISTS (SELECT 1 FROM lyrics l WHERE l."track_id" = t."id") AS "has_lyrics",
+    t."lyrics_pinned",
 
     ar."id" as "artist_id",
     ar."name" as "artist_name",
@@ -207,6 +219,7 @@ impl TrackService {
         track: &LidarrTrack,
         track_file: &LidarrTrackFile,
     ) -> Result<i64> {
+        let isrc = track.isrc();
         let row = sqlx::query_as!(
             IdRow,
             r#"INSERT INTO track (
@@ -216,10 +229,12 @@ impl TrackService {
                 "duration_ms",
                 "lidarr_id",
                 "musicbrainz_id",
+                "recording_musicbrainz_id",
+                "isrc",
                 "album_id",
                 "artist_id"
             ) VALUES (
-                $1, $2, $3, $4, $5, $6,
+                $1, $2, $3, $4, $5, $6, $10, $9,
                 (SELECT id FROM album WHERE lidarr_id = $7),
                 (SELECT id FROM artist WHERE lidarr_id = $8)
             ) ON CONFLICT(lidarr_id) DO UPDATE SET
@@ -227,7 +242,9 @@ impl TrackService {
                 "title" = $2,
                 "file_path" = $3,
                 "duration_ms" = $4,
-                "musicbrainz_id" = $6
+                "musicbrainz_id" = $6,
+                "recording_musicbrainz_id" = $10,
+                "isrc" = COALESCE($9, "isrc")
             RETURNING
                 "id""#,
             track.absolute_track_number,
@@ -238,12 +255,101 @@ impl TrackService {
             track.foreign_track_id,
             track.album_id,
             track.artist_id,
+            isrc,
+            track.foreign_recording_id,
         )
         .fetch_one(&self.pool)
         .await?;
         Ok(row.id)
     }
 
+    pub async fn find_wanted(&self, limit: u32) -> Result<Vec<Track>> {
+        let mut qb = sqlx::QueryBuilder::new(SELECT);
+        qb.push(
+            r#" WHERE NOT EXISTS (SELECT 1 FROM lyrics l WHERE l."track_id" = t."id")
+            AND t."lyrics_pinned" = FALSE
+            AND (t."next_search_at" IS NULL OR t."next_search_at" <= CURRENT_TIMESTAMP)
+            ORDER BY t."search_attempts" ASC, t."created_at" DESC, t."id" DESC
+            LIMIT "#,
+        );
+        qb.push_bind(limit);
+        let rows: Vec<TrackRow> = qb.build_query_as().fetch_all(&self.pool).await?;
+        Ok(rows.into_iter().map(Track::from).collect())
+    }
+
+    pub async fn record_search_attempt(&self, id: i64) -> Result<i64> {
+        let row = sqlx::query!(
+            r#"UPDATE track SET
+                "search_attempts" = "search_attempts" + 1,
+                "last_searched_at" = CURRENT_TIMESTAMP
+            WHERE
+                "id" = $1
+            RETURNING "search_attempts""#,
+            id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(row.search_attempts)
+    }
+
+    pub async fn set_next_search(&self, id: i64, delay: u64) -> Result<()> {
+        let delay_modifier = format!("+{} seconds", delay);
+        sqlx::query!(
+            r#"UPDATE track SET
+                "next_search_at" = datetime('now', $1)
+            WHERE
+                "id" = $2"#,
+            delay_modifier,
+            id
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn reset_search_attempts(&self, id: i64) -> Result<()> {
+        sqlx::query!(
+            r#"UPDATE track SET
+                "search_attempts" = 0,
+                "last_searched_at" = CURRENT_TIMESTAMP,
+                "next_search_at" = NULL
+            WHERE
+                "id" = $1"#,
+            id
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn set_isrc(&self, id: i64, isrc: &str) -> Result<()> {
+        sqlx::query!(
+            r#"UPDATE track SET
+                "isrc" = $1
+            WHERE
+                "id" = $2"#,
+            isrc,
+            id
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn set_lyrics_pinned(&self, id: i64, pinned: bool) -> Result<()> {
+        sqlx::query!(
+            r#"UPDATE track SET
+                "lyrics_pinned" = $1
+            WHERE
+                "id" = $2"#,
+            pinned,
+            id
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
     pub async fn remove(&self, id: i64) -> Result<()> {
         sqlx::query!(
             r#"DELETE FROM track
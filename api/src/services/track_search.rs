@@ -0,0 +1,228 @@
+use anyhow::Result;
+use sqlx::{prelude::FromRow, Pool, Sqlite};
+
+use crate::models::{
+    generic::{IdRow, Page, Pageable, TotalRow},
+    track_search::{CreateTrackSearch, TrackSearch},
+};
+
+/// Searches kept per track; older ones are dropped as new searches are recorded.
+const MAX_PER_TRACK: i64 = 20;
+
+#[derive(FromRow)]
+struct TrackSearchRow {
+    id: i64,
+    created_at: String,
+    track_id: i64,
+    job_id: Option<i64>,
+    outcome: String,
+    min_score: f64,
+    providers: String,
+    candidates: String,
+    errors: String,
+    chosen: Option<String>,
+    error: Option<String>,
+}
+
+impl TryFrom<TrackSearchRow> for TrackSearch {
+    type Error = anyhow::Error;
+
+    fn try_from(value: TrackSearchRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: value.id,
+            created_at: value.created_at,
+            track_id: value.track_id,
+            job_id: value.job_id,
+            outcome: value.outcome.parse()?,
+            min_score: value.min_score,
+            providers: serde_json::from_str(&value.providers)?,
+            candidates: serde_json::from_str(&value.candidates)?,
+            errors: serde_json::from_str(&value.errors)?,
+            chosen: match value.chosen {
+                Some(chosen) => Some(serde_json::from_str(&chosen)?),
+                None => None,
+            },
+            error: value.error,
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct TrackSearchService {
+    pool: Pool<Sqlite>,
+}
+
+impl TrackSearchService {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+
+    pub async fn create(&self, data: &CreateTrackSearch) -> Result<i64> {
+        let outcome = data.outcome.to_string();
+        let providers = serde_json::to_string(&data.providers)?;
+        let candidates = serde_json::to_string(&data.candidates)?;
+        let errors = serde_json::to_string(&data.errors)?;
+        let chosen = data
+            .chosen
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()?;
+        let row = sqlx::query_as!(
+            IdRow,
+            r#"INSERT INTO track_search (
+                "track_id",
+                "job_id",
+                "outcome",
+                "min_score",
+                "providers",
+                "candidates",
+                "errors",
+                "chosen",
+                "error"
+            ) VALUES (
+                $1, $2, $3, $4, $5, $6, $7, $8, $9
+            ) RETURNING "id""#,
+            data.track_id,
+            data.job_id,
+            outcome,
+            data.min_score,
+            providers,
+            candidates,
+            errors,
+            chosen,
+            data.error,
+        )
+        .fetch_one(&self.pool)
+        .await?;
+        self.purge(data.track_id).await?;
+        Ok(row.id)
+    }
+
+    pub async fn purge(&self, track_id: i64) -> Result<u64> {
+        let result = sqlx::query!(
+            r#"DELETE FROM track_search
+            WHERE "track_id" = $1 AND "id" NOT IN (
+                SELECT "id" FROM track_search
+                WHERE "track_id" = $1
+                ORDER BY "id" DESC
+                LIMIT $2
+            )"#,
+            track_id,
+            MAX_PER_TRACK,
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn find_page(&self, track_id: i64, pageable: &Pageable) -> Result<Page<TrackSearch>> {
+        let total = sqlx::query_as!(
+            TotalRow,
+            r#"SELECT COUNT(*) as "total" FROM track_search WHERE "track_id" = $1"#,
+            track_id
+        )
+        .fetch_one(&self.pool)
+        .await?
+        .total;
+
+        let mut qb = sqlx::QueryBuilder::new(
+            r#"SELECT
+                s."id",
+                s."created_at",
+                s."track_id",
+                s."job_id",
+                s."outcome",
+                s."min_score",
+                s."providers",
+                s."candidates",
+                s."errors",
+                s."chosen",
+                s."error"
+            FROM track_search s
+            WHERE s."track_id" = "#,
+        );
+        qb.push_bind(track_id);
+        qb.push(r#" ORDER BY s."created_at" DESC, s."id" DESC"#);
+        pageable.push_limit_offset(&mut qb);
+        let rows: Vec<TrackSearchRow> = qb.build_query_as().fetch_all(&self.pool).await?;
+        Ok(Page {
+            total,
+            items: rows
+                .into_iter()
+                .map(TrackSearch::try_from)
+                .collect::<Result<Vec<TrackSearch>>>()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        provider::{ProviderError, ProviderMetadata},
+        track_search::SearchOutcome,
+    };
+    use crate::utils::fixtures;
+
+    async fn service() -> TrackSearchService {
+        TrackSearchService::new(fixtures::pool().await)
+    }
+
+    fn search(outcome: SearchOutcome) -> CreateTrackSearch {
+        CreateTrackSearch {
+            track_id: 1,
+            job_id: Some(3),
+            outcome,
+            min_score: 0.8,
+            providers: vec!["LrcLib".into(), "Genius".into()],
+            candidates: Vec::new(),
+            errors: vec![ProviderError {
+                provider: ProviderMetadata {
+                    name: "Genius".into(),
+                },
+                error: "Timed out after 10s".into(),
+            }],
+            chosen: None,
+            error: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_and_find() {
+        let searches = service().await;
+        for outcome in [SearchOutcome::ProviderError, SearchOutcome::NoResults] {
+            searches.create(&search(outcome)).await.unwrap();
+        }
+
+        let page = searches
+            .find_page(
+                1,
+                &Pageable {
+                    page: Some(0),
+                    size: Some(1),
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(page.total, 2);
+        assert_eq!(page.items.len(), 1);
+        let search = &page.items[0];
+        assert_eq!(search.outcome, SearchOutcome::NoResults);
+        assert_eq!(search.providers, vec!["LrcLib", "Genius"]);
+        assert_eq!(search.errors[0].provider.name, "Genius");
+        assert!(search.chosen.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_retention() {
+        let searches = service().await;
+        for _ in 0..MAX_PER_TRACK + 5 {
+            searches
+                .create(&search(SearchOutcome::NoResults))
+                .await
+                .unwrap();
+        }
+        let page = searches.find_page(1, &Pageable::default()).await.unwrap();
+        assert_eq!(page.total, MAX_PER_TRACK);
+    }
+}
@@ -6,10 +6,12 @@ use sqlx::SqlitePool;
 use crate::{
     args::AppArgs,
     services::{
-        album::AlbumSerivce, artist::ArtistSerivce, event::EventService, image::ImageService,
-        job::JobService, jwt::JwtService, lidarr::LidarrService, lyrics::LyricsService,
+        album::AlbumSerivce, artist::ArtistSerivce, blocklist::BlocklistService,
+        cache::CacheService, event::EventService, image::ImageService, job::JobService,
+        job_log::JobLogService, jwt::JwtService, lidarr::LidarrService, lyrics::LyricsService,
         notifier::NotifierService, provider::ProviderService, scheduler::SchedulerService,
         search::SearchService, settings::SettingsService, stats::StatsService, track::TrackService,
+        track_search::TrackSearchService,
     },
 };
 
@@ -19,9 +21,12 @@ pub struct AppState {
 
     pub album_service: Arc<AlbumSerivce>,
     pub artist_service: Arc<ArtistSerivce>,
+    pub blocklist_service: Arc<BlocklistService>,
+    pub cache_service: Arc<CacheService>,
     pub event_service: Arc<EventService>,
     pub image_service: Arc<ImageService>,
     pub job_service: Arc<JobService>,
+    pub job_log_service: Arc<JobLogService>,
     pub jwt_service: Arc<JwtService>,
     pub lidarr_service: Arc<LidarrService>,
     pub lyrics_service: Arc<LyricsService>,
@@ -32,6 +37,7 @@ pub struct AppState {
     pub settings_service: Arc<SettingsService>,
     pub stats_service: Arc<StatsService>,
     pub track_service: Arc<TrackService>,
+    pub track_search_service: Arc<TrackSearchService>,
 }
 
 impl AppState {
@@ -41,18 +47,31 @@ impl AppState {
 
         let settings_service = Arc::new(SettingsService::from_path(&args.settings_path).await?);
 
-        let (job_service, receiver) = JobService::new(pool.clone());
-        let job_service = Arc::new(job_service);
+        let job_service = Arc::new(JobService::new(pool.clone()));
+        let job_log_service = Arc::new(JobLogService::new(pool.clone()));
 
         let mut scheduler_service = SchedulerService::new(job_service.clone()).await?;
         scheduler_service.add_default_tasks().await?;
+        scheduler_service
+            .add_log_retention(
+                "0 0 3 * * *",
+                job_log_service.clone(),
+                settings_service.clone(),
+            )
+            .await?;
         let scheduler_service = Arc::new(scheduler_service);
-        let provider_service = Arc::new(ProviderService::new());
+        let cache_service = Arc::new(CacheService::new(pool.clone()));
+        let provider_service = Arc::new(ProviderService::new(
+            settings_service.clone(),
+            cache_service.clone(),
+        ));
 
         let event_service = Arc::new(EventService::new());
         let album_service = Arc::new(AlbumSerivce::new(pool.clone()));
         let artist_service = Arc::new(ArtistSerivce::new(pool.clone()));
+        let blocklist_service = Arc::new(BlocklistService::new(pool.clone()));
         let track_service = Arc::new(TrackService::new(pool.clone()));
+        let track_search_service = Arc::new(TrackSearchService::new(pool.clone()));
         let lyrics_service = Arc::new(LyricsService::new(
             pool.clone(),
             settings_service.clone(),
@@ -68,9 +87,12 @@ impl AppState {
         let state = Arc::new(Self {
             album_service,
             artist_service,
+            blocklist_service,
+            cache_service,
             event_service,
             image_service,
             job_service,
+            job_log_service,
             jwt_service,
             lidarr_service,
             lyrics_service,
@@ -81,12 +103,17 @@ impl AppState {
             settings_service,
             stats_service,
             track_service,
+            track_search_service,
             args,
         });
 
         {
             let arc = state.clone();
-            tokio::spawn(JobService::start_worker(arc, receiver));
+            tokio::spawn(JobService::start_worker(arc));
+        }
+        {
+            let job_log_service = state.job_log_service.clone();
+            tokio::spawn(async move { job_log_service.start_worker().await });
         }
         {
             let arc = state.clone();
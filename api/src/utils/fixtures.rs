@@ -0,0 +1,67 @@
+use sqlx::{sqlite::SqlitePoolOptions, Pool, Sqlite};
+
+use crate::models::{
+    album::{Album, AlbumWithArtist},
+    artist::Artist,
+    track::Track,
+};
+
+/// In-memory database with every migration applied and a small library: one artist, tracks
+/// 1 and 2 on album 1, track 3 on album 2, and lyrics for track 2.
+pub async fn pool() -> Pool<Sqlite> {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    sqlx::migrate!("../migrations").run(&pool).await.unwrap();
+    sqlx::raw_sql(
+        r#"INSERT INTO artist ("id", "name") VALUES (1, 'Harbour Lights');
+        INSERT INTO album ("id", "title", "artist_id") VALUES (1, 'Tidewater', 1), (2, 'Low Tide', 1);
+        INSERT INTO track ("id", "title", "file_path", "album_id", "artist_id") VALUES
+            (1, 'Almost Home', '/music/1.flac', 1, 1),
+            (2, 'Evening Falls', '/music/2.flac', 1, 1),
+            (3, 'Undertow', '/music/3.flac', 2, 1);
+        INSERT INTO lyrics ("id", "synced", "file_path", "checksum", "track_id") VALUES
+            (1, TRUE, '/music/2.lrc', '', 2);"#,
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+    pool
+}
+
+pub fn track(title: &str, artist: &str) -> Track {
+    let artist = Artist {
+        id: 1,
+        name: artist.into(),
+        description: None,
+        image_path: None,
+        lidarr_id: None,
+        musicbrainz_id: None,
+    };
+    Track {
+        id: 1,
+        track_number: 1,
+        title: title.into(),
+        file_path: "".into(),
+        duration_ms: 143_000,
+        musicbrainz_id: None,
+        recording_musicbrainz_id: None,
+        isrc: None,
+        has_lyrics: false,
+        lyrics_pinned: false,
+        album: AlbumWithArtist {
+            album: Album {
+                id: 1,
+                title: "Tidewater".into(),
+                description: None,
+                cover_path: None,
+                lidarr_id: None,
+                musicbrainz_id: None,
+            },
+            artist: artist.clone(),
+        },
+        artist,
+    }
+}
@@ -50,6 +50,16 @@ impl Lrc {
     }
 }
 
+pub fn format_time(time: Duration) -> String {
+    let centis = (time.as_millis() + 5) / 10;
+    format!(
+        "{:02}:{:02}.{:02}",
+        centis / 6_000,
+        (centis / 100) % 60,
+        centis % 100
+    )
+}
+
 pub struct LrcParser {
     input: String,
     pos: usize,
@@ -159,6 +169,7 @@ impl LrcParser {
 
     fn parse_lyric_line(&mut self) -> Option<LrcLyricLine> {
         self.skip_whitespace();
+        self.peek()?;
         let time = self.backtrack(Self::parse_time);
         let mut lyric = String::new();
         loop {
@@ -266,6 +277,22 @@ mod tests {
         assert_eq!(lrc.lyrics[4].lyric, String::from("Line 5"));
     }
 
+    #[test]
+    fn test_lyrics_trailing_newline() {
+        let mut parser = LrcParser::new("[00:12.34] Line 1\n[00:15.10] Line 2\n".into());
+        let lrc = parser.parse();
+        assert_eq!(lrc.lyrics.len(), 2);
+        assert_eq!(lrc.lyrics_type(), LyricsType::Synced);
+    }
+
+    #[test]
+    fn test_format_time() {
+        assert_eq!(format_time(Duration::from_millis(0)), "00:00.00");
+        assert_eq!(format_time(Duration::from_millis(16_240)), "00:16.24");
+        assert_eq!(format_time(Duration::from_millis(83_005)), "01:23.01");
+        assert_eq!(format_time(Duration::from_secs(3_683)), "61:23.00");
+    }
+
     #[test]
     fn test_real() {
         let mut parser = LrcParser::new(
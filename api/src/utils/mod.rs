@@ -1,4 +1,8 @@
 pub mod audiodb;
 pub mod checksum;
 pub mod de;
+#[cfg(test)]
+pub mod fixtures;
 pub mod lrc;
+pub mod normalize;
+pub mod tags;
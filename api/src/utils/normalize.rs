@@ -0,0 +1,198 @@
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+
+use crate::models::settings::NormalizationSettings;
+
+const FEATURE_MARKERS: [&str; 4] = ["feat.", "feat ", "ft.", "featuring "];
+const SUFFIX_KEYWORDS: [&str; 13] = [
+    "remaster",
+    "remastered",
+    "live",
+    "edition",
+    "version",
+    "deluxe",
+    "mono",
+    "stereo",
+    "radio edit",
+    "anniversary",
+    "bonus",
+    "acoustic",
+    "explicit",
+];
+
+fn is_feature(content: &str) -> bool {
+    let content = content.trim_start().to_lowercase();
+    FEATURE_MARKERS
+        .iter()
+        .any(|marker| content.starts_with(marker))
+}
+
+fn is_suffix(content: &str) -> bool {
+    // Pad with spaces so keywords only match whole words, e.g. not "live" in "Alive"
+    let words = content
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<&str>>()
+        .join(" ");
+    let words = format!(" {} ", words);
+    SUFFIX_KEYWORDS
+        .iter()
+        .any(|keyword| words.contains(&format!(" {} ", keyword)))
+}
+
+fn strip_groups(value: &str, strip: impl Fn(&str) -> bool) -> String {
+    let mut output = String::new();
+    let mut rest = value;
+    while let Some(start) = rest.find(['(', '[']) {
+        let close = if rest[start..].starts_with('(') {
+            ')'
+        } else {
+            ']'
+        };
+        let Some(length) = rest[start + 1..].find(close) else {
+            break;
+        };
+        let end = start + 1 + length;
+        output.push_str(&rest[..start]);
+        if !strip(&rest[start + 1..end]) {
+            output.push_str(&rest[start..=end]);
+        }
+        rest = &rest[end + 1..];
+    }
+    output.push_str(rest);
+    output
+}
+
+fn strip_trailing_features(value: &str) -> &str {
+    let lowercase = value.to_ascii_lowercase();
+    [" feat. ", " feat ", " ft. ", " featuring "]
+        .iter()
+        .filter_map(|marker| lowercase.find(marker))
+        .min()
+        .map(|index| &value[..index])
+        .unwrap_or(value)
+}
+
+fn strip_dash_suffix(value: &str) -> &str {
+    match value.rfind(" - ") {
+        Some(index) if is_suffix(&value[index + 3..]) => &value[..index],
+        _ => value,
+    }
+}
+
+pub fn normalize(value: &str, settings: &NormalizationSettings) -> String {
+    let mut output = value.to_string();
+
+    if settings.strip_features {
+        output = strip_groups(&output, is_feature);
+        output = strip_trailing_features(&output).to_string();
+    }
+    if settings.strip_suffixes {
+        output = strip_groups(&output, is_suffix);
+        output = strip_dash_suffix(&output).to_string();
+    }
+    if settings.strip_diacritics {
+        output = output.nfd().filter(|c| !is_combining_mark(*c)).collect();
+    }
+    if settings.strip_punctuation {
+        output = output
+            .chars()
+            .filter(|c| !matches!(c, '\'' | '’'))
+            .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+            .collect();
+    }
+    if settings.lowercase {
+        output = output.to_lowercase();
+    }
+
+    output.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn all() -> NormalizationSettings {
+        NormalizationSettings {
+            lowercase: true,
+            strip_punctuation: true,
+            strip_features: true,
+            strip_suffixes: true,
+            strip_diacritics: true,
+        }
+    }
+
+    #[test]
+    fn test_normalize_suffixes() {
+        assert_eq!(
+            normalize("Almost Home (Remastered 2011)", &all()),
+            "almost home"
+        );
+        assert_eq!(
+            normalize("Almost Home - 2011 Remaster", &all()),
+            "almost home"
+        );
+        assert_eq!(
+            normalize("Almost Home [Live at Wembley]", &all()),
+            "almost home"
+        );
+        assert_eq!(normalize("Tidewater (Deluxe Edition)", &all()), "tidewater");
+        assert_eq!(
+            normalize("Almost Home (Reprise)", &all()),
+            "almost home reprise"
+        );
+        assert_eq!(normalize("Low Tide - Part 2", &all()), "low tide part 2");
+        assert_eq!(normalize("Still (Alive)", &all()), "still alive");
+        assert_eq!(
+            normalize("Monument (Monolith)", &all()),
+            "monument monolith"
+        );
+    }
+
+    #[test]
+    fn test_normalize_features() {
+        assert_eq!(
+            normalize("Almost Home (feat. Someone)", &all()),
+            "almost home"
+        );
+        assert_eq!(
+            normalize("Almost Home [ft. Someone]", &all()),
+            "almost home"
+        );
+        assert_eq!(
+            normalize("Almost Home feat. Someone", &all()),
+            "almost home"
+        );
+        assert_eq!(
+            normalize("Harbour Lights featuring Someone", &all()),
+            "harbour lights"
+        );
+        assert_eq!(
+            normalize("Stay (with Someone)", &all()),
+            "stay with someone"
+        );
+    }
+
+    #[test]
+    fn test_normalize_characters() {
+        assert_eq!(normalize("Beyoncé", &all()), "beyonce");
+        assert_eq!(normalize("Don't Stop Me Now!", &all()), "dont stop me now");
+        assert_eq!(normalize("AC/DC", &all()), "ac dc");
+        assert_eq!(normalize("晴天", &all()), "晴天");
+    }
+
+    #[test]
+    fn test_normalize_disabled() {
+        let settings = NormalizationSettings {
+            lowercase: false,
+            strip_punctuation: false,
+            strip_features: false,
+            strip_suffixes: false,
+            strip_diacritics: false,
+        };
+        assert_eq!(
+            normalize("  Beyoncé  (Remastered)  ", &settings),
+            "Beyoncé (Remastered)"
+        );
+    }
+}
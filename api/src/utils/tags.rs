@@ -0,0 +1,131 @@
+use std::{
+    fs::File,
+    io::{BufReader, Read, Seek, SeekFrom},
+    path::Path,
+};
+
+use anyhow::Result;
+use id3::TagLike;
+
+const FLAC_VORBIS_COMMENT: u8 = 4;
+
+fn clean_isrc(value: &str) -> Option<String> {
+    let isrc: String = value
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_uppercase();
+    (isrc.len() == 12).then_some(isrc)
+}
+
+fn read_u32_le(reader: &mut impl Read) -> Result<u32> {
+    let mut buffer = [0; 4];
+    reader.read_exact(&mut buffer)?;
+    Ok(u32::from_le_bytes(buffer))
+}
+
+pub fn parse_vorbis_comments(reader: &mut impl Read) -> Result<Vec<(String, String)>> {
+    let vendor_length = read_u32_le(reader)?;
+    std::io::copy(&mut reader.take(vendor_length as u64), &mut std::io::sink())?;
+    let count = read_u32_le(reader)?;
+    let mut comments = Vec::new();
+    for _ in 0..count {
+        let length = read_u32_le(reader)?;
+        let mut buffer = vec![0; length as usize];
+        reader.read_exact(&mut buffer)?;
+        let comment = String::from_utf8_lossy(&buffer);
+        if let Some((key, value)) = comment.split_once('=') {
+            comments.push((key.to_uppercase(), value.to_string()));
+        }
+    }
+    Ok(comments)
+}
+
+pub fn read_flac_comments(reader: &mut (impl Read + Seek)) -> Result<Vec<(String, String)>> {
+    let mut magic = [0; 4];
+    reader.read_exact(&mut magic)?;
+    if &magic != b"fLaC" {
+        anyhow::bail!("Not a FLAC file");
+    }
+    loop {
+        let mut header = [0; 4];
+        reader.read_exact(&mut header)?;
+        let last = header[0] & 0x80 != 0;
+        let block_type = header[0] & 0x7f;
+        let length = u32::from_be_bytes([0, header[1], header[2], header[3]]);
+        if block_type == FLAC_VORBIS_COMMENT {
+            return parse_vorbis_comments(&mut reader.take(length as u64));
+        }
+        if last {
+            return Ok(Vec::new());
+        }
+        reader.seek(SeekFrom::Current(length as i64))?;
+    }
+}
+
+pub fn read_isrc(path: &Path) -> Result<Option<String>> {
+    let extension = path
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let isrc = match extension.as_str() {
+        "mp3" => {
+            let tag = match id3::Tag::read_from_path(path) {
+                Ok(tag) => tag,
+                Err(e) if matches!(e.kind, id3::ErrorKind::NoTag) => return Ok(None),
+                Err(e) => return Err(e.into()),
+            };
+            tag.get("TSRC")
+                .and_then(|frame| frame.content().text())
+                .and_then(clean_isrc)
+        }
+        "flac" => {
+            let mut reader = BufReader::new(File::open(path)?);
+            read_flac_comments(&mut reader)?
+                .into_iter()
+                .filter(|(key, _)| key == "ISRC")
+                .find_map(|(_, value)| clean_isrc(&value))
+        }
+        _ => None,
+    };
+    Ok(isrc)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn vorbis_comments(comments: &[&str]) -> Vec<u8> {
+        let mut block = Vec::new();
+        block.extend(6u32.to_le_bytes());
+        block.extend(b"vendor");
+        block.extend((comments.len() as u32).to_le_bytes());
+        for comment in comments {
+            block.extend((comment.len() as u32).to_le_bytes());
+            block.extend(comment.as_bytes());
+        }
+        block
+    }
+
+    #[test]
+    fn test_read_flac_comments() {
+        let comments = vorbis_comments(&["TITLE=Almost Home", "isrc=GB-AYE-11-00001"]);
+        let mut data = b"fLaC".to_vec();
+        data.extend([0x00, 0x00, 0x00, 0x02, 0xaa, 0xbb]);
+        data.push(0x80 | FLAC_VORBIS_COMMENT);
+        data.extend(&(comments.len() as u32).to_be_bytes()[1..]);
+        data.extend(comments);
+
+        let comments = read_flac_comments(&mut Cursor::new(data)).unwrap();
+        assert_eq!(
+            comments,
+            vec![
+                ("TITLE".into(), "Almost Home".into()),
+                ("ISRC".into(), "GB-AYE-11-00001".into())
+            ]
+        );
+        assert_eq!(clean_isrc(&comments[1].1).as_deref(), Some("GBAYE1100001"));
+        assert_eq!(clean_isrc("invalid"), None);
+    }
+}
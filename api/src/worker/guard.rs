@@ -0,0 +1,374 @@
+use std::{
+    future::Future,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, Result};
+use reqwest::StatusCode;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::models::{
+    provider::{CircuitState, ProviderStatus},
+    settings::ProviderLimitsSettings,
+};
+
+/// Rate limits, server errors and network failures of an HTTP request.
+pub fn is_retryable(error: &anyhow::Error) -> bool {
+    error
+        .chain()
+        .filter_map(|cause| cause.downcast_ref::<reqwest::Error>())
+        .any(|error| match error.status() {
+            Some(status) => status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error(),
+            None => error.is_timeout() || error.is_connect(),
+        })
+}
+
+/// SQLite lock contention or a pool timeout, which go away once other writers are done.
+pub fn is_database_busy(error: &anyhow::Error) -> bool {
+    error
+        .chain()
+        .filter_map(|cause| cause.downcast_ref::<sqlx::Error>())
+        .any(|error| match error {
+            sqlx::Error::PoolTimedOut => true,
+            sqlx::Error::Database(error) => {
+                matches!(error.code().as_deref(), Some("5") | Some("6"))
+            }
+            _ => false,
+        })
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Timed out after {}s", .0.as_secs())]
+pub struct ProviderTimeout(pub Duration);
+
+/// Runs a provider call that isn't wrapped in a guard with the same timeout.
+pub async fn with_timeout<T>(timeout: Duration, f: impl Future<Output = Result<T>>) -> Result<T> {
+    tokio::time::timeout(timeout, f)
+        .await
+        .map_err(|_| ProviderTimeout(timeout))?
+}
+
+struct Circuit {
+    consecutive_failures: u32,
+    open_until: Option<Instant>,
+    last_error: Option<String>,
+    /// Whether a half-open circuit already let a request through to probe the provider.
+    probing: bool,
+}
+
+/// Releases the half-open probe, even when the probing call is dropped before it finishes.
+struct Probe<'a>(&'a Mutex<Circuit>);
+
+impl Drop for Probe<'_> {
+    fn drop(&mut self) {
+        self.0.lock().unwrap().probing = false;
+    }
+}
+
+struct InFlight {
+    size: usize,
+    /// Permits still to be removed after a shrink, taken back as running calls release them.
+    shrink: usize,
+    semaphore: Arc<Semaphore>,
+}
+
+pub struct ProviderGuard {
+    name: &'static str,
+    circuit: Mutex<Circuit>,
+    in_flight: Mutex<InFlight>,
+    next_slot: Mutex<Instant>,
+}
+
+impl ProviderGuard {
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            circuit: Mutex::new(Circuit {
+                consecutive_failures: 0,
+                open_until: None,
+                last_error: None,
+                probing: false,
+            }),
+            in_flight: Mutex::new(InFlight {
+                size: 0,
+                shrink: 0,
+                semaphore: Arc::new(Semaphore::new(0)),
+            }),
+            next_slot: Mutex::new(Instant::now()),
+        }
+    }
+
+    pub fn status(&self) -> ProviderStatus {
+        let circuit = self.circuit.lock().unwrap();
+        let now = Instant::now();
+        let (state, retry_after_secs) = match circuit.open_until {
+            Some(open_until) if open_until > now => (
+                CircuitState::Open,
+                Some((open_until - now).as_secs_f64().ceil() as u64),
+            ),
+            Some(_) => (CircuitState::HalfOpen, None),
+            None => (CircuitState::Closed, None),
+        };
+        ProviderStatus {
+            name: self.name.into(),
+            state,
+            consecutive_failures: circuit.consecutive_failures,
+            retry_after_secs,
+            last_error: circuit.last_error.clone(),
+        }
+    }
+
+    fn check_circuit(&self) -> Result<Option<Probe<'_>>> {
+        let mut circuit = self.circuit.lock().unwrap();
+        match circuit.open_until {
+            Some(open_until) if open_until > Instant::now() || circuit.probing => Err(anyhow!(
+                "Provider {} is paused after {} consecutive failures",
+                self.name,
+                circuit.consecutive_failures
+            )),
+            Some(_) => {
+                circuit.probing = true;
+                Ok(Some(Probe(&self.circuit)))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn record_success(&self) {
+        let mut circuit = self.circuit.lock().unwrap();
+        circuit.consecutive_failures = 0;
+        circuit.open_until = None;
+    }
+
+    fn record_failure(&self, error: &anyhow::Error, limits: &ProviderLimitsSettings) {
+        let mut circuit = self.circuit.lock().unwrap();
+        circuit.consecutive_failures += 1;
+        circuit.last_error = Some(error.to_string());
+        let half_open = circuit.open_until.is_some();
+        if half_open || circuit.consecutive_failures >= limits.failure_threshold.max(1) {
+            circuit.open_until = Some(Instant::now() + Duration::from_secs(limits.cooldown));
+        }
+    }
+
+    async fn acquire(&self, limits: &ProviderLimitsSettings) -> Result<OwnedSemaphorePermit> {
+        let semaphore = {
+            let mut in_flight = self.in_flight.lock().unwrap();
+            let size = limits.max_in_flight.max(1);
+            // Resize in place so permits held by running calls still count against the new size
+            if size > in_flight.size {
+                let grow = size - in_flight.size;
+                let cancelled = grow.min(in_flight.shrink);
+                in_flight.shrink -= cancelled;
+                in_flight.semaphore.add_permits(grow - cancelled);
+            } else {
+                in_flight.shrink += in_flight.size - size;
+            }
+            in_flight.size = size;
+            if in_flight.shrink > 0 {
+                in_flight.shrink -= in_flight.semaphore.forget_permits(in_flight.shrink);
+            }
+            in_flight.semaphore.clone()
+        };
+        let permit = semaphore.acquire_owned().await?;
+
+        if limits.requests_per_second > 0.0 {
+            let slot = {
+                let mut next_slot = self.next_slot.lock().unwrap();
+                let slot = (*next_slot).max(Instant::now());
+                *next_slot = slot + Duration::from_secs_f64(1.0 / limits.requests_per_second);
+                slot
+            };
+            tokio::time::sleep_until(slot.into()).await;
+        }
+
+        Ok(permit)
+    }
+
+    /// Runs `f` within the provider's limits; `timeout` applies to each attempt, not to the time
+    /// spent waiting for a slot.
+    pub async fn run<T, F, Fut>(
+        &self,
+        limits: &ProviderLimitsSettings,
+        timeout: Duration,
+        f: F,
+    ) -> Result<T>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let _probe = self.check_circuit()?;
+
+        let mut attempt: u32 = 0;
+        loop {
+            let result = {
+                let _permit = self.acquire(limits).await?;
+                with_timeout(timeout, f()).await
+            };
+            match result {
+                Ok(value) => {
+                    self.record_success();
+                    return Ok(value);
+                }
+                Err(e) if attempt < limits.max_retries && is_retryable(&e) => {
+                    let backoff = limits.retry_backoff_ms.saturating_mul(1 << attempt.min(16));
+                    println!(
+                        "[{}] Retrying in {}ms after error: {}",
+                        self.name, backoff, e
+                    );
+                    tokio::time::sleep(Duration::from_millis(backoff)).await;
+                    attempt += 1;
+                }
+                Err(e) => {
+                    self.record_failure(&e, limits);
+                    return Err(e);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    fn limits() -> ProviderLimitsSettings {
+        ProviderLimitsSettings {
+            requests_per_second: 0.0,
+            max_in_flight: 2,
+            max_retries: 2,
+            retry_backoff_ms: 1,
+            failure_threshold: 2,
+            cooldown: 60,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_circuit_opens_after_failures() {
+        let guard = ProviderGuard::new("Test");
+        let calls = AtomicU32::new(0);
+        let fail = || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err::<(), _>(anyhow!("boom"))
+        };
+
+        assert!(guard.run(&limits(), TIMEOUT, fail).await.is_err());
+        assert_eq!(guard.status().state, CircuitState::Closed);
+        assert!(guard.run(&limits(), TIMEOUT, fail).await.is_err());
+        assert_eq!(guard.status().state, CircuitState::Open);
+        assert_eq!(guard.status().last_error.as_deref(), Some("boom"));
+
+        let error = guard.run(&limits(), TIMEOUT, fail).await.unwrap_err();
+        assert!(error.to_string().contains("paused"));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_half_open_closes_on_success() {
+        let guard = ProviderGuard::new("Test");
+        let mut limits = limits();
+        limits.failure_threshold = 1;
+        limits.cooldown = 0;
+
+        assert!(guard
+            .run(&limits, TIMEOUT, || async { Err::<(), _>(anyhow!("boom")) })
+            .await
+            .is_err());
+        assert_eq!(guard.status().state, CircuitState::HalfOpen);
+
+        guard
+            .run(&limits, TIMEOUT, || async { Ok(()) })
+            .await
+            .unwrap();
+        let status = guard.status();
+        assert_eq!(status.state, CircuitState::Closed);
+        assert_eq!(status.consecutive_failures, 0);
+    }
+
+    #[tokio::test]
+    async fn test_max_in_flight() {
+        let guard = ProviderGuard::new("Test");
+        let current = AtomicU32::new(0);
+        let peak = AtomicU32::new(0);
+        let task = || async {
+            let now = current.fetch_add(1, Ordering::SeqCst) + 1;
+            peak.fetch_max(now, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            current.fetch_sub(1, Ordering::SeqCst);
+            Ok(())
+        };
+        let limits = limits();
+        futures::future::try_join_all((0..6).map(|_| guard.run(&limits, TIMEOUT, task)))
+            .await
+            .unwrap();
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_half_open_allows_one_probe() {
+        let guard = ProviderGuard::new("Test");
+        let mut limits = limits();
+        limits.failure_threshold = 1;
+        limits.cooldown = 0;
+
+        assert!(guard
+            .run(&limits, TIMEOUT, || async { Err::<(), _>(anyhow!("boom")) })
+            .await
+            .is_err());
+        let probe = || async {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            Ok(())
+        };
+        let (first, second) = tokio::join!(
+            guard.run(&limits, TIMEOUT, probe),
+            guard.run(&limits, TIMEOUT, probe)
+        );
+        assert!(first.is_ok());
+        assert!(second.unwrap_err().to_string().contains("paused"));
+        assert_eq!(guard.status().state, CircuitState::Closed);
+    }
+
+    #[tokio::test]
+    async fn test_attempt_timeout_is_a_failure() {
+        let guard = ProviderGuard::new("Test");
+        let mut limits = limits();
+        limits.max_retries = 0;
+        let error = guard
+            .run(&limits, Duration::from_millis(10), || async {
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                Ok(())
+            })
+            .await
+            .unwrap_err();
+        assert!(error.is::<ProviderTimeout>());
+        assert_eq!(guard.status().consecutive_failures, 1);
+    }
+
+    #[tokio::test]
+    async fn test_in_flight_resize() {
+        let guard = ProviderGuard::new("Test");
+        let mut limits = limits();
+        let first = guard.acquire(&limits).await.unwrap();
+        let second = guard.acquire(&limits).await.unwrap();
+        limits.max_in_flight = 1;
+        // Shrinking does not hand out new permits while both calls are still running
+        assert!(
+            tokio::time::timeout(Duration::from_millis(10), guard.acquire(&limits))
+                .await
+                .is_err()
+        );
+        drop(first);
+        assert!(
+            tokio::time::timeout(Duration::from_millis(10), guard.acquire(&limits))
+                .await
+                .is_err()
+        );
+        drop(second);
+        let _third = guard.acquire(&limits).await.unwrap();
+        limits.max_in_flight = 2;
+        assert!(guard.acquire(&limits).await.is_ok());
+    }
+}
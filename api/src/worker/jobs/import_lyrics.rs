@@ -12,6 +12,8 @@ use crate::{
 #[serde(rename_all = "camelCase")]
 pub struct ImportLyricsParams {
     pub provider: Option<String>,
+    #[serde(default)]
+    pub identifier: Option<String>,
     pub track_id: i64,
     pub content: String,
     pub synced: bool,
@@ -61,6 +63,7 @@ pub async fn import_lyrics(context: JobContext<ImportLyricsParams>) -> Result<()
                 synced: context.params.synced,
                 track_id: context.params.track_id,
                 provider: context.params.provider.clone(),
+                provider_identifier: context.params.identifier.clone(),
                 language: None,
             })
             .await?;
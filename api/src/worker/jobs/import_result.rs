@@ -0,0 +1,65 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    models::job::JobContext,
+    worker::jobs::import_lyrics::{import_lyrics, ImportLyricsParams},
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportResultParams {
+    pub track_id: i64,
+    pub provider: String,
+    pub identifier: String,
+    #[serde(default)]
+    pub pin: bool,
+}
+
+pub async fn import_result(context: JobContext<ImportResultParams>) -> Result<()> {
+    let track = context
+        .state
+        .track_service
+        .find(context.params.track_id)
+        .await?;
+
+    // Resolved from the provider (or its cached search) rather than from anything the client
+    // sent, so the attribution and content are the provider's own
+    let result = context
+        .state
+        .provider_service
+        .find_result(&track, &context.params.provider, &context.params.identifier)
+        .await?;
+
+    context.log(format!(
+        "Importing {} result: {} - {} - {}",
+        result.provider.name,
+        result.file.artist_name,
+        result.file.album_title,
+        result.file.track_name,
+    ));
+
+    let content = match &result.file.content {
+        Some(content) => content.clone(),
+        None => context.state.provider_service.download(&result).await?,
+    };
+
+    import_lyrics(context.clone_with_params(ImportLyricsParams {
+        provider: Some(result.provider.name),
+        identifier: Some(result.file.identifier),
+        track_id: track.id,
+        content,
+        synced: result.file.synced,
+    }))
+    .await?;
+
+    if context.params.pin {
+        context
+            .state
+            .track_service
+            .set_lyrics_pinned(track.id, true)
+            .await?;
+    }
+
+    Ok(())
+}
@@ -1,6 +1,9 @@
 pub mod clean_album;
 
 pub mod import_lyrics;
+pub mod import_result;
+
+pub mod publish_lyrics;
 
 pub mod remove_album;
 pub mod remove_artist;
@@ -15,6 +18,7 @@ pub mod search_album;
 pub mod search_artist;
 pub mod search_library;
 pub mod search_track;
+pub mod search_wanted;
 
 pub mod sync_album;
 pub mod sync_album_metadata;
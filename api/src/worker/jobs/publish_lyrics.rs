@@ -0,0 +1,58 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    models::job::JobContext,
+    utils::lrc::{LrcParser, LyricsType},
+    worker::providers::lrclib::{LrcLibProvider, LrcLibPublishBody},
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublishLyricsParams {
+    pub lyrics_id: i64,
+}
+
+pub async fn publish_lyrics(context: JobContext<PublishLyricsParams>) -> Result<()> {
+    let lyrics = context
+        .state
+        .lyrics_service
+        .find(context.params.lyrics_id)
+        .await?;
+    let content = context
+        .state
+        .lyrics_service
+        .get_content(lyrics.id)
+        .await?
+        .text;
+
+    let lrc = LrcParser::new(content.clone()).parse();
+    let plain_lyrics = lrc
+        .lyrics
+        .iter()
+        .map(|line| line.lyric.as_str())
+        .collect::<Vec<&str>>()
+        .join("\n");
+    let synced_lyrics = match lrc.lyrics_type() {
+        LyricsType::Synced => Some(content),
+        _ => None,
+    };
+
+    let body = LrcLibPublishBody::from_track(&lyrics.track, plain_lyrics, synced_lyrics);
+
+    context.log(format!(
+        "Publishing lyrics for track: {}",
+        lyrics.track.title
+    ));
+
+    let result = LrcLibProvider::new().publish(&body).await;
+    let error = result.as_ref().err().map(|e| e.to_string());
+
+    context
+        .state
+        .lyrics_service
+        .set_published(lyrics.id, &error)
+        .await?;
+
+    result
+}
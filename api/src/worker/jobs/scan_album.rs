@@ -2,7 +2,10 @@ use anyhow::Result;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    models::{job::JobContext, track::TracksFilters},
+    models::{
+        job::{JobContext, JobProgress},
+        track::TracksFilters,
+    },
     worker::jobs::{
         clean_album::{clean_album, CleanAlbumParams},
         scan_track::{scan_track, ScanTrackParams},
@@ -30,12 +33,12 @@ pub async fn scan_album(context: JobContext<ScanAlbumParams>) -> Result<()> {
         .await?;
 
     for (i, track) in tracks.iter().enumerate() {
-        context.log(format!(
-            "[{}/{}] Scanning track: {}",
-            i + 1,
-            tracks.len(),
-            track.title
-        ));
+        context.check_cancelled()?;
+        context
+            .progress(
+                JobProgress::new(i + 1, tracks.len(), "Scanning track").with_entity(&track.title),
+            )
+            .await;
         scan_track(context.clone_with_params(ScanTrackParams { track_id: track.id })).await?;
     }
 
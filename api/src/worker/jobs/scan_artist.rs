@@ -2,7 +2,10 @@ use anyhow::Result;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    models::{album::AlbumsFilters, job::JobContext},
+    models::{
+        album::AlbumsFilters,
+        job::{JobContext, JobProgress},
+    },
     worker::jobs::scan_album::{scan_album, ScanAlbumParams},
 };
 
@@ -25,12 +28,13 @@ pub async fn scan_artist(context: JobContext<ScanArtistParams>) -> Result<()> {
         .await?;
 
     for (i, album) in albums.iter().enumerate() {
-        context.log(format!(
-            "[{}/{}] Scanning album: {}",
-            i + 1,
-            albums.len(),
-            album.album.title
-        ));
+        context.check_cancelled()?;
+        context
+            .progress(
+                JobProgress::new(i + 1, albums.len(), "Scanning album")
+                    .with_entity(&album.album.title),
+            )
+            .await;
         scan_album(context.clone_with_params(ScanAlbumParams {
             album_id: album.album.id,
         }))
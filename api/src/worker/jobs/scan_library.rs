@@ -1,7 +1,7 @@
 use anyhow::Result;
 
 use crate::{
-    models::job::JobContext,
+    models::job::{JobContext, JobProgress},
     worker::jobs::scan_artist::{scan_artist, ScanArtistParams},
 };
 
@@ -9,12 +9,13 @@ pub async fn scan_library(context: JobContext<()>) -> Result<()> {
     let albums = context.state.artist_service.find_many(None).await?;
 
     for (i, item) in albums.iter().enumerate() {
-        context.log(format!(
-            "[{}/{}] Scanning album: {}",
-            i + 1,
-            albums.len(),
-            item.artist.name
-        ));
+        context.check_cancelled()?;
+        context
+            .progress(
+                JobProgress::new(i + 1, albums.len(), "Scanning artist")
+                    .with_entity(&item.artist.name),
+            )
+            .await;
         scan_artist(context.clone_with_params(ScanArtistParams {
             artist_id: item.artist.id,
         }))
@@ -1,10 +1,10 @@
 use anyhow::Result;
-use futures::{stream::FuturesUnordered, TryStreamExt};
+use futures::{stream, StreamExt};
 use serde::{Deserialize, Serialize};
 
 use crate::{
     models::{
-        job::JobContext,
+        job::{JobContext, JobProgress},
         track::{Track, TracksFilters},
     },
     worker::jobs::search_track::{search_track, SearchTrackParams},
@@ -32,29 +32,40 @@ pub async fn search_album(context: JobContext<SearchAlbumParams>) -> Result<()>
 
     let filtered: Vec<Track> = tracks.into_iter().filter(|t| !t.has_lyrics).collect();
 
-    let mut futures = FuturesUnordered::new();
-
-    for track in filtered.iter() {
-        futures.push(search_track(
-            context.clone_with_params(SearchTrackParams { track_id: track.id }),
-        ));
-    }
+    // Searching more tracks at once than providers take requests would only queue them up
+    let concurrency = context
+        .state
+        .settings_service
+        .get()
+        .await
+        .providers
+        .limits
+        .max_in_flight
+        .max(1);
+    let total = filtered.len();
+    let mut futures = stream::iter(filtered)
+        .map(|track| {
+            let context = context.clone_with_params(SearchTrackParams { track_id: track.id });
+            async move { (track, search_track(context).await) }
+        })
+        .buffer_unordered(concurrency);
 
     let mut i: usize = 0;
-    loop {
-        context.log(format!("[{}/{}] Searching tracks", i, filtered.len()));
-        let result = futures.try_next().await;
-        match result {
-            Err(e) => {
-                eprintln!("Error while searching track {}: {}", filtered[i].title, e);
-            }
-            Ok(o) => {
-                if o.is_none() {
-                    break;
-                }
-            }
-        }
+    context
+        .progress(JobProgress::new(i, total, "Searching tracks"))
+        .await;
+    while let Some((track, result)) = futures.next().await {
         i += 1;
+        context.check_cancelled()?;
+        if let Err(e) = result {
+            context.warn(format!(
+                "Error while searching track {}: {}",
+                track.title, e
+            ));
+        }
+        context
+            .progress(JobProgress::new(i, total, "Searching tracks").with_entity(&track.title))
+            .await;
     }
 
     Ok(())
@@ -25,13 +25,17 @@ pub async fn search_artist(context: JobContext<SearchArtistParams>) -> Result<()
         .await?;
 
     for item in albums.iter() {
+        context.check_cancelled()?;
         if item.stats.with_lyrics_count < item.stats.tracks_count {
             if let Err(e) = search_album(context.clone_with_params(SearchAlbumParams {
                 album_id: item.album.id,
             }))
             .await
             {
-                eprintln!("Error while searching album {}: {}", item.album.title, e);
+                context.warn(format!(
+                    "Error while searching album {}: {}",
+                    item.album.title, e
+                ));
             }
         }
     }
@@ -1,26 +1,35 @@
 use anyhow::Result;
 
 use crate::{
-    models::job::JobContext,
+    models::job::{JobContext, JobProgress},
     worker::jobs::search_artist::{search_artist, SearchArtistParams},
 };
 
 pub async fn search_library(context: JobContext<()>) -> Result<()> {
+    let purged = context.state.cache_service.purge_expired().await?;
+    if purged > 0 {
+        context.log(format!("Purged {} expired provider cache entries", purged));
+    }
+
     let albums = context.state.artist_service.find_many(None).await?;
 
     for (i, item) in albums.iter().enumerate() {
-        context.log(format!(
-            "[{}/{}] Searching album: {}",
-            i + 1,
-            albums.len(),
-            item.artist.name
-        ));
+        context.check_cancelled()?;
+        context
+            .progress(
+                JobProgress::new(i + 1, albums.len(), "Searching artist")
+                    .with_entity(&item.artist.name),
+            )
+            .await;
         if let Err(e) = search_artist(context.clone_with_params(SearchArtistParams {
             artist_id: item.artist.id,
         }))
         .await
         {
-            eprintln!("Error while searching album {}: {}", item.artist.name, e);
+            context.warn(format!(
+                "Error while searching artist {}: {}",
+                item.artist.name, e
+            ));
         }
     }
 
@@ -2,7 +2,11 @@ use anyhow::Result;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    models::{job::JobContext, provider::ProviderResult},
+    models::{
+        job::JobContext,
+        provider::ProviderResult,
+        track_search::{CreateTrackSearch, SearchCandidate, SearchOutcome},
+    },
     worker::jobs::import_lyrics::{import_lyrics, ImportLyricsParams},
 };
 
@@ -12,6 +16,25 @@ pub struct SearchTrackParams {
     pub track_id: i64,
 }
 
+async fn download_and_import(
+    context: &JobContext<SearchTrackParams>,
+    best: ProviderResult,
+) -> Result<()> {
+    let content = if let Some(content) = best.file.content {
+        content
+    } else {
+        context.state.provider_service.download(&best).await?
+    };
+    import_lyrics(context.clone_with_params(ImportLyricsParams {
+        provider: Some(best.provider.name),
+        identifier: Some(best.file.identifier),
+        track_id: context.params.track_id,
+        content,
+        synced: best.file.synced,
+    }))
+    .await
+}
+
 pub async fn search_track(context: JobContext<SearchTrackParams>) -> Result<()> {
     let settings = context.state.settings_service.get().await;
 
@@ -21,24 +44,44 @@ pub async fn search_track(context: JobContext<SearchTrackParams>) -> Result<()>
         .find(context.params.track_id)
         .await?;
 
-    let results = context.state.provider_service.get_results(&track).await?;
+    if track.lyrics_pinned {
+        context.log(format!("Skipping pinned track: {}", track.title));
+        return Ok(());
+    }
 
-    for result in &results {
-        println!(
-            "Found result with score {:.2}: {:} - {:} - {:} (synced: {:})",
-            result.score,
-            result.file.artist_name,
-            result.file.album_title,
-            result.file.track_name,
-            result.file.synced,
-        );
+    let results = context.state.provider_service.get_results(&track).await;
+    let blocklist = context
+        .state
+        .blocklist_service
+        .find_by_track(track.id)
+        .await?;
+
+    for error in &results.errors {
+        context.warn(format!(
+            "Provider {} failed: {}",
+            error.provider.name, error.error
+        ));
     }
 
+    let mut candidates = Vec::new();
     let mut best_opt: Option<ProviderResult> = None;
-    for result in results {
+    let mut blocklisted_count = 0;
+    for result in results.results {
+        let blocklisted = blocklist.iter().any(|entry| {
+            entry.provider == result.provider.name && entry.identifier == result.file.identifier
+        });
+        candidates.push(SearchCandidate::new(&result, blocklisted));
         if result.score < settings.lyrics.min_score {
             continue;
         }
+        if blocklisted {
+            context.log(format!(
+                "Skipping blocklisted {} result: {}",
+                result.provider.name, result.file.identifier
+            ));
+            blocklisted_count += 1;
+            continue;
+        }
         if let Some(best) = &best_opt {
             if (!best.file.synced && result.file.synced) || best.score < result.score {
                 best_opt = Some(result);
@@ -48,20 +91,75 @@ pub async fn search_track(context: JobContext<SearchTrackParams>) -> Result<()>
         }
     }
 
-    if let Some(best) = best_opt {
-        let content = if let Some(content) = best.file.content {
-            content
-        } else {
-            context.state.provider_service.download(&best).await?
-        };
-        import_lyrics(context.clone_with_params(ImportLyricsParams {
-            provider: Some(best.provider.name),
-            track_id: context.params.track_id,
-            content,
-            synced: best.file.synced,
-        }))
-        .await?;
+    let mut search = CreateTrackSearch {
+        track_id: track.id,
+        job_id: Some(context.job_id),
+        outcome: SearchOutcome::NoResults,
+        min_score: settings.lyrics.min_score,
+        providers: results
+            .providers
+            .into_iter()
+            .map(|provider| provider.name)
+            .collect(),
+        candidates,
+        errors: results.errors,
+        chosen: None,
+        error: None,
+    };
+
+    let imported = match best_opt {
+        Some(best) => {
+            context.log(format!(
+                "Selected {} result with score {:.2}: {} - {} - {}",
+                best.provider.name,
+                best.score,
+                best.file.artist_name,
+                best.file.album_title,
+                best.file.track_name,
+            ));
+            search.chosen = Some(SearchCandidate::new(&best, false));
+            let imported = download_and_import(&context, best).await;
+            match &imported {
+                Ok(()) => search.outcome = SearchOutcome::Imported,
+                Err(e) => {
+                    search.outcome = SearchOutcome::ImportFailed;
+                    search.error = Some(e.to_string());
+                }
+            }
+            imported
+        }
+        None => {
+            search.outcome = if blocklisted_count > 0 {
+                SearchOutcome::Blocklisted
+            } else if !search.candidates.is_empty() {
+                SearchOutcome::BelowMinScore
+            } else if !search.errors.is_empty() {
+                SearchOutcome::ProviderError
+            } else {
+                SearchOutcome::NoResults
+            };
+            context.log(format!(
+                "No lyrics selected for {}: {}",
+                track.title, search.outcome
+            ));
+            Ok(())
+        }
+    };
+
+    context.state.track_search_service.create(&search).await?;
+
+    let track_service = &context.state.track_service;
+    if search.outcome == SearchOutcome::Imported {
+        track_service.reset_search_attempts(track.id).await?;
+    } else {
+        let attempts = track_service.record_search_attempt(track.id).await?;
+        let mut delay = settings.wanted.backoff_after(attempts);
+        if settings.cache.enabled {
+            // Searching again before the cached misses expire would only hit the cache
+            delay = delay.max(settings.cache.miss_ttl);
+        }
+        track_service.set_next_search(track.id, delay).await?;
     }
 
-    Ok(())
+    imported
 }
@@ -0,0 +1,55 @@
+use anyhow::Result;
+use futures::{stream, StreamExt};
+
+use crate::{
+    models::job::{JobContext, JobProgress},
+    worker::jobs::search_track::{search_track, SearchTrackParams},
+};
+
+pub async fn search_wanted(context: JobContext<()>) -> Result<()> {
+    let settings = context.state.settings_service.get().await.wanted;
+
+    let purged = context.state.cache_service.purge_expired().await?;
+    if purged > 0 {
+        context.log(format!("Purged {} expired provider cache entries", purged));
+    }
+
+    let tracks = context
+        .state
+        .track_service
+        .find_wanted(settings.budget)
+        .await?;
+
+    context.log(format!(
+        "Searching {} wanted tracks (budget: {})",
+        tracks.len(),
+        settings.budget
+    ));
+
+    let total = tracks.len();
+    let mut results = stream::iter(tracks)
+        .map(|track| {
+            let context = context.clone_with_params(SearchTrackParams { track_id: track.id });
+            async move { (track, search_track(context).await) }
+        })
+        .buffer_unordered(settings.concurrency.max(1));
+
+    let mut i: usize = 0;
+    while let Some((track, result)) = results.next().await {
+        i += 1;
+        context.check_cancelled()?;
+        if let Err(e) = result {
+            context.warn(format!(
+                "Error while searching track {}: {}",
+                track.title, e
+            ));
+        }
+        context
+            .progress(
+                JobProgress::new(i, total, "Searching wanted tracks").with_entity(&track.title),
+            )
+            .await;
+    }
+
+    Ok(())
+}
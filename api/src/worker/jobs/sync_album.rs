@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 
@@ -6,6 +8,7 @@ use crate::{
         job::JobContext,
         lidarr::{LidarrTrackFileQuery, LidarrTrackQuery},
     },
+    utils::tags::read_isrc,
     worker::jobs::{
         remove_track::{remove_track, RemoveTrackParams},
         sync_album_metadata::{sync_album_metadata, SyncAlbumMetadataParams},
@@ -18,6 +21,23 @@ pub struct SyncAlbumParams {
     pub album_id: i64,
 }
 
+async fn sync_track_isrc(context: &JobContext<SyncAlbumParams>, track_id: i64) -> Result<()> {
+    let track = context.state.track_service.find(track_id).await?;
+    if track.isrc.is_some() {
+        return Ok(());
+    }
+    let root_folder = context.state.settings_service.get().await.root_folder;
+    let path = PathBuf::from(root_folder).join(track.relative_file_path());
+    if let Some(isrc) = tokio::task::spawn_blocking(move || read_isrc(&path)).await?? {
+        context
+            .state
+            .track_service
+            .set_isrc(track_id, &isrc)
+            .await?;
+    }
+    Ok(())
+}
+
 pub async fn sync_album(context: JobContext<SyncAlbumParams>) -> Result<()> {
     let album = context
         .state
@@ -60,6 +80,12 @@ pub async fn sync_album(context: JobContext<SyncAlbumParams>) -> Result<()> {
                 .track_service
                 .upsert_lidarr(&lidarr_track, &lidarr_track_file)
                 .await?;
+            if let Err(e) = sync_track_isrc(&context, track_id).await {
+                context.log(format!(
+                    "Failed to read tags of {}: {}",
+                    lidarr_track_file.path, e
+                ));
+            }
             ids.push(track_id);
         }
     }
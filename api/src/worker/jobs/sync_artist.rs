@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     models::{
-        job::JobContext,
+        job::{JobContext, JobProgress},
         lidarr::{LidarrAlbum, LidarrAlbumQuery},
     },
     worker::jobs::{
@@ -52,12 +52,13 @@ pub async fn sync_artist(context: JobContext<SyncArtistParams>) -> Result<()> {
     let mut ids = Vec::new();
 
     for (i, lidarr_album) in albums.iter().enumerate() {
-        context.log(format!(
-            "[{}/{}] Syncing album: {}",
-            i + 1,
-            albums.len(),
-            lidarr_album.title
-        ));
+        context.check_cancelled()?;
+        context
+            .progress(
+                JobProgress::new(i + 1, albums.len(), "Syncing album")
+                    .with_entity(&lidarr_album.title),
+            )
+            .await;
         let album_id = context
             .state
             .album_service
@@ -1,7 +1,10 @@
 use anyhow::Result;
 
 use crate::{
-    models::{job::JobContext, lidarr::LidarrArtist},
+    models::{
+        job::{JobContext, JobProgress},
+        lidarr::LidarrArtist,
+    },
     worker::jobs::{
         remove_artist::{remove_artist, RemoveArtistParams},
         sync_artist::{sync_artist, SyncArtistParams},
@@ -21,12 +24,13 @@ pub async fn sync_library(context: JobContext<()>) -> Result<()> {
     let mut ids = Vec::new();
 
     for (i, lidarr_artist) in artists.iter().enumerate() {
-        context.log(format!(
-            "[{}/{}] Syncing artist: {}",
-            i + 1,
-            artists.len(),
-            lidarr_artist.artist_name
-        ));
+        context.check_cancelled()?;
+        context
+            .progress(
+                JobProgress::new(i + 1, artists.len(), "Syncing artist")
+                    .with_entity(&lidarr_artist.artist_name),
+            )
+            .await;
         let artist_id = context
             .state
             .artist_service
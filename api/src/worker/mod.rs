@@ -2,4 +2,6 @@ pub mod jobs;
 pub mod notifiers;
 pub mod providers;
 
+pub mod guard;
+pub mod pool;
 pub mod provider;
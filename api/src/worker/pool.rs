@@ -0,0 +1,150 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+};
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::models::job::JobLane;
+
+struct Lane {
+    size: usize,
+    /// Permits still to be removed after a shrink, taken back as running jobs release them.
+    shrink: usize,
+    semaphore: Arc<Semaphore>,
+}
+
+pub struct WorkerPermit {
+    _permit: OwnedSemaphorePermit,
+    key: String,
+    active: Arc<Mutex<HashSet<String>>>,
+}
+
+impl Drop for WorkerPermit {
+    fn drop(&mut self) {
+        self.active.lock().unwrap().remove(&self.key);
+    }
+}
+
+#[derive(Default)]
+pub struct WorkerPool {
+    lanes: Mutex<HashMap<JobLane, Lane>>,
+    active: Arc<Mutex<HashSet<String>>>,
+}
+
+impl WorkerPool {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn semaphore(&self, lane: JobLane, size: usize) -> Arc<Semaphore> {
+        let mut lanes = self.lanes.lock().unwrap();
+        let size = size.max(1);
+        let lane = lanes.entry(lane).or_insert_with(|| Lane {
+            size,
+            shrink: 0,
+            semaphore: Arc::new(Semaphore::new(size)),
+        });
+        // Resize in place so permits held by running jobs still count against the new size
+        if size > lane.size {
+            let grow = size - lane.size;
+            let cancelled = grow.min(lane.shrink);
+            lane.shrink -= cancelled;
+            lane.semaphore.add_permits(grow - cancelled);
+        } else {
+            lane.shrink += lane.size - size;
+        }
+        lane.size = size;
+        if lane.shrink > 0 {
+            lane.shrink -= lane.semaphore.forget_permits(lane.shrink);
+        }
+        lane.semaphore.clone()
+    }
+
+    /// Keys are entity paths such as `artist:1/album:2/`; a key conflicts with any active key
+    /// that it contains or that contains it.
+    pub fn try_acquire(&self, lane: JobLane, size: usize, key: &str) -> Option<WorkerPermit> {
+        let mut active = self.active.lock().unwrap();
+        if active
+            .iter()
+            .any(|other| other.starts_with(key) || key.starts_with(other.as_str()))
+        {
+            return None;
+        }
+        let permit = self.semaphore(lane, size).try_acquire_owned().ok()?;
+        active.insert(key.to_string());
+        Some(WorkerPermit {
+            _permit: permit,
+            key: key.to_string(),
+            active: self.active.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRACK_1: &str = "artist:1/album:1/track:1/";
+    const TRACK_2: &str = "artist:1/album:1/track:2/";
+    const TRACK_3: &str = "artist:1/album:2/track:3/";
+    const LIBRARY: &str = "library/";
+
+    #[test]
+    fn test_same_entity_is_serialized() {
+        let pool = WorkerPool::new();
+        let permit = pool.try_acquire(JobLane::Search, 4, TRACK_1).unwrap();
+        assert!(pool.try_acquire(JobLane::User, 4, TRACK_1).is_none());
+        drop(permit);
+        assert!(pool.try_acquire(JobLane::User, 4, TRACK_1).is_some());
+        assert!(pool.active.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parent_entity_is_serialized() {
+        let pool = WorkerPool::new();
+        let _album = pool
+            .try_acquire(JobLane::Search, 4, "artist:1/album:1/")
+            .unwrap();
+        assert!(pool.try_acquire(JobLane::User, 4, TRACK_1).is_none());
+        assert!(pool.try_acquire(JobLane::User, 4, "artist:1/").is_none());
+        assert!(pool.try_acquire(JobLane::User, 4, TRACK_3).is_some());
+    }
+
+    #[test]
+    fn test_library_does_not_block_entities() {
+        let pool = WorkerPool::new();
+        let _library = pool.try_acquire(JobLane::Library, 4, LIBRARY).unwrap();
+        assert!(pool.try_acquire(JobLane::Library, 4, LIBRARY).is_none());
+        assert!(pool.try_acquire(JobLane::User, 4, TRACK_1).is_some());
+        assert!(pool.try_acquire(JobLane::Search, 4, "artist:2/").is_some());
+    }
+
+    #[test]
+    fn test_lane_concurrency() {
+        let pool = WorkerPool::new();
+        let first = pool.try_acquire(JobLane::Search, 2, TRACK_1).unwrap();
+        let _second = pool.try_acquire(JobLane::Search, 2, TRACK_2).unwrap();
+        assert!(pool.try_acquire(JobLane::Search, 2, TRACK_3).is_none());
+        assert!(pool.try_acquire(JobLane::Library, 1, "artist:2/").is_some());
+        assert!(pool.try_acquire(JobLane::User, 1, TRACK_3).is_some());
+        drop(first);
+        assert!(pool.try_acquire(JobLane::Search, 2, "artist:3/").is_some());
+    }
+
+    #[test]
+    fn test_lane_resize() {
+        let pool = WorkerPool::new();
+        let first = pool.try_acquire(JobLane::Search, 2, "artist:1/").unwrap();
+        let second = pool.try_acquire(JobLane::Search, 2, "artist:2/").unwrap();
+        // Shrinking does not hand out new permits while both jobs are still running
+        assert!(pool.try_acquire(JobLane::Search, 1, "artist:3/").is_none());
+        drop(first);
+        assert!(pool.try_acquire(JobLane::Search, 1, "artist:3/").is_none());
+        drop(second);
+        let third = pool.try_acquire(JobLane::Search, 1, "artist:3/").unwrap();
+        assert!(pool.try_acquire(JobLane::Search, 1, "artist:4/").is_none());
+        assert!(pool.try_acquire(JobLane::Search, 2, "artist:4/").is_some());
+        drop(third);
+    }
+}
@@ -1,11 +1,64 @@
 use anyhow::Result;
 use async_trait::async_trait;
 
-use crate::models::{provider::ProviderFile, track::Track};
+use crate::models::{
+    job::log_current_job, provider::ProviderFile, settings::ProvidersSettings, track::Track,
+};
+
+/// Keeps the lookups that succeeded and logs the others, so one bad song doesn't discard the
+/// rest of a search. Fails only when every lookup did.
+pub fn partial_results<T>(name: &str, results: Vec<Result<T>>) -> Result<Vec<T>> {
+    let mut values = Vec::new();
+    let mut first_error = None;
+    for result in results {
+        match result {
+            Ok(value) => values.push(value),
+            Err(e) => {
+                log_current_job(format!("[{}] Skipping result: {}", name, e));
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+    match first_error {
+        Some(e) if values.is_empty() => Err(e),
+        _ => Ok(values),
+    }
+}
 
 #[async_trait]
 pub trait LyricsProvider: Send + Sync {
     fn name(&self) -> &'static str;
+    fn remote(&self) -> bool {
+        true
+    }
+    fn enabled(&self, _settings: &ProvidersSettings) -> bool {
+        true
+    }
+    /// Settings that change what a search returns, so cached results don't outlive them.
+    fn cache_scope(&self, _settings: &ProvidersSettings) -> String {
+        String::new()
+    }
     async fn search_lyrics(&self, track: &Track) -> Result<Vec<ProviderFile>>;
     async fn download(&self, result: &ProviderFile) -> Result<String>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+
+    #[test]
+    fn test_partial_results() {
+        let results = vec![Ok(1), Err(anyhow!("boom")), Ok(3)];
+        assert_eq!(partial_results("Test", results).unwrap(), vec![1, 3]);
+
+        let results: Vec<Result<i32>> = vec![Err(anyhow!("boom")), Err(anyhow!("bang"))];
+        assert_eq!(
+            partial_results("Test", results).unwrap_err().to_string(),
+            "boom"
+        );
+        assert!(partial_results::<i32>("Test", Vec::new())
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,257 @@
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use futures::future::join_all;
+use reqwest::{Client, Url};
+use scraper::{ElementRef, Html, Node, Selector};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{
+    models::{provider::ProviderFile, settings::ProvidersSettings, track::Track},
+    services::settings::SettingsService,
+    worker::provider::{partial_results, LyricsProvider},
+};
+
+const MAX_SONGS: usize = 5;
+
+#[derive(Serialize)]
+pub struct GeniusSearchQuery {
+    pub q: String,
+}
+
+#[derive(Deserialize)]
+pub struct GeniusResponse<T> {
+    pub response: T,
+}
+
+#[derive(Deserialize)]
+pub struct GeniusSearchResponse {
+    pub hits: Vec<GeniusHit>,
+}
+
+#[derive(Deserialize)]
+pub struct GeniusHit {
+    #[serde(rename = "type")]
+    pub hit_type: String,
+    pub result: GeniusSong,
+}
+
+#[derive(Deserialize)]
+pub struct GeniusSongResponse {
+    pub song: GeniusSong,
+}
+
+#[derive(Deserialize)]
+pub struct GeniusSong {
+    pub id: i64,
+    pub title: String,
+    pub full_title: String,
+    pub url: String,
+    pub primary_artist: GeniusArtist,
+    pub album: Option<GeniusAlbum>,
+}
+
+#[derive(Deserialize)]
+pub struct GeniusArtist {
+    pub name: String,
+}
+
+#[derive(Deserialize)]
+pub struct GeniusAlbum {
+    pub name: String,
+}
+
+pub struct GeniusProvider {
+    base_url: String,
+    settings_service: Arc<SettingsService>,
+    client: Client,
+}
+
+impl GeniusProvider {
+    pub fn new(settings_service: Arc<SettingsService>) -> Self {
+        Self {
+            base_url: "https://api.genius.com/".into(),
+            settings_service,
+            client: Client::new(),
+        }
+    }
+
+    async fn access_token(&self) -> Option<String> {
+        let settings = self.settings_service.get().await.providers.genius;
+        if settings.enabled {
+            settings.access_token
+        } else {
+            None
+        }
+    }
+
+    pub async fn request<T: DeserializeOwned, Q: Serialize>(
+        &self,
+        path: &str,
+        query: Option<Q>,
+    ) -> Result<T> {
+        let access_token = self
+            .access_token()
+            .await
+            .ok_or(anyhow!("Genius access token is not configured"))?;
+        let mut url = Url::parse(&self.base_url).unwrap();
+        url.set_path(path);
+        if let Some(q) = query {
+            let query_string = serde_qs::to_string(&q)?;
+            url.set_query(Some(&query_string));
+        }
+        println!("[GENIUS] Fetch '{}'", url);
+        let builder = self.client.get(url).bearer_auth(access_token);
+        let response = builder.send().await?.error_for_status()?;
+        Ok(response.json::<GeniusResponse<T>>().await?.response)
+    }
+
+    pub async fn search(&self, q: String) -> Result<GeniusSearchResponse> {
+        self.request("search", Some(GeniusSearchQuery { q })).await
+    }
+
+    pub async fn retrieve(&self, id: i64) -> Result<GeniusSong> {
+        let response: GeniusSongResponse =
+            self.request(&format!("songs/{}", id), None::<()>).await?;
+        Ok(response.song)
+    }
+
+    pub async fn fetch_page(&self, url: &str) -> Result<String> {
+        println!("[GENIUS] Fetch '{}'", url);
+        let response = self.client.get(url).send().await?.error_for_status()?;
+        Ok(response.text().await?)
+    }
+}
+
+fn push_text(element: ElementRef, output: &mut String) {
+    for child in element.children() {
+        match child.value() {
+            Node::Text(text) => output.push_str(text),
+            Node::Element(e) if e.name() == "br" => output.push('\n'),
+            Node::Element(e) if e.attr("data-exclude-from-selection").is_some() => {}
+            Node::Element(_) => {
+                if let Some(child_element) = ElementRef::wrap(child) {
+                    push_text(child_element, output);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+pub fn extract_lyrics(html: &str) -> Option<String> {
+    let document = Html::parse_document(html);
+    let selector = Selector::parse(r#"[data-lyrics-container="true"]"#).unwrap();
+    let mut lyrics = String::new();
+    for container in document.select(&selector) {
+        if !lyrics.is_empty() {
+            lyrics.push('\n');
+        }
+        push_text(container, &mut lyrics);
+    }
+    let lyrics = lyrics.trim();
+    if lyrics.is_empty() {
+        None
+    } else {
+        Some(lyrics.into())
+    }
+}
+
+#[async_trait]
+impl LyricsProvider for GeniusProvider {
+    fn name(&self) -> &'static str {
+        "Genius"
+    }
+
+    fn enabled(&self, settings: &ProvidersSettings) -> bool {
+        settings.genius.enabled
+            && settings
+                .genius
+                .access_token
+                .as_deref()
+                .is_some_and(|token| !token.trim().is_empty())
+    }
+
+    async fn search_lyrics(&self, track: &Track) -> Result<Vec<ProviderFile>> {
+        if self.access_token().await.is_none() {
+            return Ok(Vec::new());
+        }
+
+        let results = self
+            .search(format!("{} {}", track.title, track.artist.name))
+            .await?;
+
+        let songs = join_all(
+            results
+                .hits
+                .into_iter()
+                .filter(|hit| hit.hit_type == "song")
+                .take(MAX_SONGS)
+                .map(|hit| self.retrieve(hit.result.id)),
+        )
+        .await;
+        let songs = partial_results(self.name(), songs)?;
+
+        Ok(songs
+            .into_iter()
+            .map(|song| ProviderFile {
+                identifier: song.id.to_string(),
+                name: song.full_title,
+                track_name: song.title,
+                artist_name: song.primary_artist.name,
+                album_title: song.album.map(|a| a.name).unwrap_or_default(),
+                synced: false,
+                exact: false,
+                translation: false,
+                duration_ms: None,
+                isrc: None,
+                musicbrainz_id: None,
+                content: None,
+            })
+            .collect())
+    }
+
+    async fn download(&self, file: &ProviderFile) -> Result<String> {
+        let song = self.retrieve(file.identifier.parse()?).await?;
+        let html = self.fetch_page(&song.url).await?;
+        extract_lyrics(&html).ok_or(anyhow!("No lyrics found on Genius page"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_lyrics() {
+        let html = include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/genius/song.html"
+        ));
+        let lyrics = extract_lyrics(html).unwrap();
+        assert_eq!(
+            lyrics,
+            "[Verse 1]\n\
+            Morning light on the harbour wall\n\
+            Gulls are singing & the tide is low\n\
+            \n\
+            [Chorus]\n\
+            Hold on, hold on\n\
+            We're almost home\n\
+            \n\
+            [Verse 2]\n\
+            Evening falls on the \"quiet\" town\n\
+            Lanterns swaying as the sun goes down"
+        );
+    }
+
+    #[test]
+    fn test_extract_lyrics_instrumental() {
+        let html = include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/genius/instrumental.html"
+        ));
+        assert_eq!(extract_lyrics(html), None);
+    }
+}
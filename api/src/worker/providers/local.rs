@@ -0,0 +1,327 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use tokio::sync::RwLock;
+
+use crate::{
+    models::{provider::ProviderFile, settings::ProvidersSettings, track::Track},
+    services::settings::SettingsService,
+    utils::lrc::{LrcParser, LyricsType},
+    worker::provider::LyricsProvider,
+};
+
+const INDEX_TTL: Duration = Duration::from_secs(600);
+const MIN_TITLE_SCORE: f64 = 0.85;
+const MAX_RESULTS: usize = 10;
+
+#[derive(Debug, Clone)]
+pub struct LocalIndexEntry {
+    pub path: PathBuf,
+    pub title: String,
+    pub artist: String,
+    pub album: String,
+    pub duration_ms: Option<i64>,
+    pub synced: bool,
+}
+
+pub struct LocalIndex {
+    pub root: String,
+    pub built_at: Instant,
+    pub entries: Vec<LocalIndexEntry>,
+}
+
+impl LocalIndex {
+    fn is_fresh(&self, root: &str) -> bool {
+        self.root == root && self.built_at.elapsed() < INDEX_TTL
+    }
+}
+
+fn parse_length(value: &str) -> Option<i64> {
+    let (minutes, seconds) = value.trim().split_once(':')?;
+    let minutes: i64 = minutes.trim().parse().ok()?;
+    let seconds: f64 = seconds.trim().parse().ok()?;
+    Some(minutes * 60_000 + (seconds * 1_000.0) as i64)
+}
+
+fn strip_track_number(stem: &str) -> &str {
+    let rest = stem.trim_start_matches(|c: char| c.is_ascii_digit());
+    let stripped = rest.trim_start_matches(['.', '-', '_', ' ']);
+    let separator = &rest[..rest.len() - stripped.len()];
+    if rest.len() == stem.len() || stripped.is_empty() || !separator.contains(['.', '-', '_']) {
+        stem
+    } else {
+        stripped
+    }
+}
+
+pub fn index_file(root: &Path, path: &Path) -> Result<LocalIndexEntry> {
+    let relative_path = path.strip_prefix(root)?.to_path_buf();
+    let content = std::fs::read_to_string(path)?;
+    let lrc = LrcParser::new(content).parse();
+
+    let components: Vec<String> = relative_path
+        .parent()
+        .map(|parent| {
+            parent
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy().to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let mut entry = LocalIndexEntry {
+        title: strip_track_number(&stem).into(),
+        artist: components
+            .len()
+            .checked_sub(2)
+            .map(|i| components[i].clone())
+            .unwrap_or_default(),
+        album: components.last().cloned().unwrap_or_default(),
+        duration_ms: None,
+        synced: lrc.lyrics_type() == LyricsType::Synced,
+        path: relative_path,
+    };
+
+    for tag in lrc.tags {
+        if tag.value.is_empty() {
+            continue;
+        }
+        match tag.tag.as_str() {
+            "ti" => entry.title = tag.value,
+            "ar" => entry.artist = tag.value,
+            "al" => entry.album = tag.value,
+            "length" => entry.duration_ms = parse_length(&tag.value),
+            _ => {}
+        }
+    }
+
+    Ok(entry)
+}
+
+fn walk(root: &Path, dir: &Path, entries: &mut Vec<LocalIndexEntry>) -> Result<()> {
+    for dir_entry in std::fs::read_dir(dir)? {
+        let dir_entry = dir_entry?;
+        let file_type = dir_entry.file_type()?;
+        let path = dir_entry.path();
+        if file_type.is_symlink() && path.is_dir() {
+            // Symlinked directories may loop back into the tree, so they are not followed
+            continue;
+        }
+        if file_type.is_dir() {
+            // An unreadable folder shouldn't take the rest of the library down with it
+            if let Err(e) = walk(root, &path, entries) {
+                eprintln!("Failed to index {}: {}", path.to_string_lossy(), e);
+            }
+        } else if path.extension().is_some_and(|ext| ext == "lrc") {
+            match index_file(root, &path) {
+                Ok(entry) => entries.push(entry),
+                Err(e) => eprintln!("Failed to index {}: {}", path.to_string_lossy(), e),
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn build_index(root: &str) -> Result<Vec<LocalIndexEntry>> {
+    let mut entries = Vec::new();
+    let root_path = PathBuf::from(root);
+    walk(&root_path, &root_path, &mut entries)?;
+    Ok(entries)
+}
+
+fn similarity(a: &str, b: &str) -> f64 {
+    strsim::jaro_winkler(&a.to_lowercase(), &b.to_lowercase())
+}
+
+pub fn find_entries<'a>(entries: &'a [LocalIndexEntry], track: &Track) -> Vec<&'a LocalIndexEntry> {
+    let mut matches: Vec<(f64, &LocalIndexEntry)> = entries
+        .iter()
+        .filter_map(|entry| {
+            let title_score = similarity(&entry.title, &track.title);
+            if title_score < MIN_TITLE_SCORE {
+                return None;
+            }
+            Some((
+                title_score + similarity(&entry.artist, &track.artist.name),
+                entry,
+            ))
+        })
+        .collect();
+    matches.sort_by(|a, b| b.0.total_cmp(&a.0));
+    matches
+        .into_iter()
+        .take(MAX_RESULTS)
+        .map(|(_, entry)| entry)
+        .collect()
+}
+
+pub struct LocalProvider {
+    settings_service: Arc<SettingsService>,
+    index: RwLock<Option<LocalIndex>>,
+}
+
+impl LocalProvider {
+    pub fn new(settings_service: Arc<SettingsService>) -> Self {
+        Self {
+            settings_service,
+            index: RwLock::new(None),
+        }
+    }
+
+    async fn refresh_index(&self, root: &str) -> Result<()> {
+        if self
+            .index
+            .read()
+            .await
+            .as_ref()
+            .is_some_and(|index| index.is_fresh(root))
+        {
+            return Ok(());
+        }
+        let mut index = self.index.write().await;
+        // Another search may have rebuilt the index while we waited for the lock
+        if index.as_ref().is_some_and(|index| index.is_fresh(root)) {
+            return Ok(());
+        }
+        println!("[LOCAL] Indexing '{}'", root);
+        let root_string = String::from(root);
+        let entries = tokio::task::spawn_blocking(move || build_index(&root_string)).await??;
+        *index = Some(LocalIndex {
+            root: root.into(),
+            built_at: Instant::now(),
+            entries,
+        });
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl LyricsProvider for LocalProvider {
+    fn name(&self) -> &'static str {
+        "Local"
+    }
+
+    fn remote(&self) -> bool {
+        false
+    }
+
+    fn enabled(&self, settings: &ProvidersSettings) -> bool {
+        settings.local.enabled && !settings.local.path.is_empty()
+    }
+
+    async fn search_lyrics(&self, track: &Track) -> Result<Vec<ProviderFile>> {
+        let settings = self.settings_service.get().await.providers.local;
+        if !settings.enabled || settings.path.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        self.refresh_index(&settings.path).await?;
+
+        let index = self.index.read().await;
+        let Some(index) = index.as_ref() else {
+            return Ok(Vec::new());
+        };
+
+        Ok(find_entries(&index.entries, track)
+            .into_iter()
+            .map(|entry| ProviderFile {
+                identifier: entry.path.to_string_lossy().to_string(),
+                name: entry.path.to_string_lossy().to_string(),
+                track_name: entry.title.clone(),
+                artist_name: entry.artist.clone(),
+                album_title: entry.album.clone(),
+                synced: entry.synced,
+                exact: false,
+                translation: false,
+                duration_ms: entry.duration_ms,
+                isrc: None,
+                musicbrainz_id: None,
+                content: None,
+            })
+            .collect())
+    }
+
+    async fn download(&self, file: &ProviderFile) -> Result<String> {
+        let settings = self.settings_service.get().await.providers.local;
+        let root = tokio::fs::canonicalize(&settings.path).await?;
+        let path = tokio::fs::canonicalize(root.join(&file.identifier)).await?;
+        if !path.starts_with(&root) {
+            return Err(anyhow!(
+                "Lyrics file is outside of the local provider folder"
+            ));
+        }
+        Ok(tokio::fs::read_to_string(&path).await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::fixtures::track;
+
+    fn fixture_root() -> String {
+        format!("{}/tests/fixtures/local", env!("CARGO_MANIFEST_DIR"))
+    }
+
+    fn sorted_index() -> Vec<LocalIndexEntry> {
+        let mut entries = build_index(&fixture_root()).unwrap();
+        entries.sort_by(|a, b| a.path.cmp(&b.path));
+        entries
+    }
+
+    #[test]
+    fn test_index_from_path() {
+        let entries = sorted_index();
+        assert_eq!(entries.len(), 3);
+
+        assert_eq!(
+            entries[0].path,
+            PathBuf::from("Harbour Lights/Tidewater/01 - Almost Home.lrc")
+        );
+        assert_eq!(entries[0].title, "Almost Home");
+        assert_eq!(entries[0].artist, "Harbour Lights");
+        assert_eq!(entries[0].album, "Tidewater");
+        assert_eq!(entries[0].duration_ms, None);
+        assert!(entries[0].synced);
+
+        assert_eq!(entries[1].title, "Low Tide");
+        assert!(!entries[1].synced);
+    }
+
+    #[test]
+    fn test_index_from_tags() {
+        let entries = sorted_index();
+        assert_eq!(entries[2].path, PathBuf::from("unsorted/track.lrc"));
+        assert_eq!(entries[2].title, "Evening Falls");
+        assert_eq!(entries[2].artist, "Harbour Lights");
+        assert_eq!(entries[2].album, "Tidewater");
+        assert_eq!(entries[2].duration_ms, Some(143_000));
+    }
+
+    #[test]
+    fn test_find_entries() {
+        let entries = sorted_index();
+        let found = find_entries(&entries, &track("Almost home", "Harbour Lights"));
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].title, "Almost Home");
+        assert!(find_entries(&entries, &track("Something Else", "Nobody")).is_empty());
+    }
+
+    #[test]
+    fn test_strip_track_number() {
+        assert_eq!(strip_track_number("01 - Almost Home"), "Almost Home");
+        assert_eq!(strip_track_number("3. Low Tide"), "Low Tide");
+        assert_eq!(strip_track_number("1979"), "1979");
+        assert_eq!(strip_track_number("7 Rings"), "7 Rings");
+        assert_eq!(strip_track_number("Almost Home"), "Almost Home");
+    }
+}
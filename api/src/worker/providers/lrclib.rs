@@ -1,11 +1,12 @@
 use crate::{
-    models::{provider::ProviderFile, track::Track},
+    models::{job::log_current_job, provider::ProviderFile, track::Track},
     worker::provider::LyricsProvider,
 };
 use anyhow::Result;
 use async_trait::async_trait;
-use reqwest::{Client, Url};
+use reqwest::{Client, Response, StatusCode, Url};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
 #[derive(Serialize, Default)]
 pub struct LrcLibLyricsQuery {
@@ -15,6 +16,28 @@ pub struct LrcLibLyricsQuery {
     pub album_name: Option<String>,
 }
 
+#[derive(Serialize)]
+pub struct LrcLibSignatureQuery {
+    pub track_name: String,
+    pub artist_name: String,
+    pub album_name: String,
+    pub duration: i64,
+}
+
+impl LrcLibSignatureQuery {
+    pub fn from_track(track: &Track) -> Option<Self> {
+        if track.duration_ms <= 0 {
+            return None;
+        }
+        Some(Self {
+            track_name: track.title.clone(),
+            artist_name: track.artist.name.clone(),
+            album_name: track.album.album.title.clone(),
+            duration: (track.duration_ms as f64 / 1_000.0).round() as i64,
+        })
+    }
+}
+
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LrcLibLyrics {
@@ -25,10 +48,55 @@ pub struct LrcLibLyrics {
     pub album_name: String,
     pub duration: f64,
     pub instrumental: bool,
+    pub plain_lyrics: Option<String>,
+    pub synced_lyrics: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LrcLibChallenge {
+    pub prefix: String,
+    pub target: String,
+}
+
+impl LrcLibChallenge {
+    pub fn solve(&self) -> String {
+        let target = self.target.to_lowercase();
+        let mut nonce: u64 = 0;
+        loop {
+            let hash = Sha256::digest(format!("{}{}", self.prefix, nonce));
+            if format!("{:x}", hash) <= target {
+                return format!("{}:{}", self.prefix, nonce);
+            }
+            nonce += 1;
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LrcLibPublishBody {
+    pub track_name: String,
+    pub artist_name: String,
+    pub album_name: String,
+    pub duration: f64,
     pub plain_lyrics: String,
     pub synced_lyrics: Option<String>,
 }
 
+impl LrcLibPublishBody {
+    pub fn from_track(track: &Track, plain_lyrics: String, synced_lyrics: Option<String>) -> Self {
+        Self {
+            track_name: track.title.clone(),
+            artist_name: track.artist.name.clone(),
+            album_name: track.album.album.title.clone(),
+            duration: track.duration_ms as f64 / 1_000.0,
+            plain_lyrics,
+            synced_lyrics,
+        }
+    }
+}
+
 pub struct LrcLibProvider {
     base_url: String,
     client: Client,
@@ -42,11 +110,7 @@ impl LrcLibProvider {
         }
     }
 
-    pub async fn request<T: DeserializeOwned, Q: Serialize>(
-        &self,
-        path: &str,
-        query: Option<Q>,
-    ) -> Result<T> {
+    async fn send<Q: Serialize>(&self, path: &str, query: Option<Q>) -> Result<Response> {
         let mut url = Url::parse(&self.base_url).unwrap();
         url.set_path(path);
         if let Some(q) = query {
@@ -54,8 +118,28 @@ impl LrcLibProvider {
             url.set_query(Some(&query_string));
         }
         println!("[LRCLIB] Fetch '{}'", url);
-        let builder = self.client.get(url);
-        Ok(builder.send().await?.json::<T>().await?)
+        Ok(self.client.get(url).send().await?)
+    }
+
+    pub async fn request<T: DeserializeOwned, Q: Serialize>(
+        &self,
+        path: &str,
+        query: Option<Q>,
+    ) -> Result<T> {
+        let response = self.send(path, query).await?;
+        Ok(response.error_for_status()?.json::<T>().await?)
+    }
+
+    pub async fn request_optional<T: DeserializeOwned, Q: Serialize>(
+        &self,
+        path: &str,
+        query: Option<Q>,
+    ) -> Result<Option<T>> {
+        let response = self.send(path, query).await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        Ok(Some(response.error_for_status()?.json::<T>().await?))
     }
 
     pub async fn search(&self, query: Option<LrcLibLyricsQuery>) -> Result<Vec<LrcLibLyrics>> {
@@ -66,6 +150,85 @@ impl LrcLibProvider {
         self.request(&format!("api/get/{:}", id).as_str(), None::<()>)
             .await
     }
+
+    pub async fn get_cached(&self, query: &LrcLibSignatureQuery) -> Result<Option<LrcLibLyrics>> {
+        self.request_optional("api/get-cached", Some(query)).await
+    }
+
+    pub async fn get(&self, query: &LrcLibSignatureQuery) -> Result<Option<LrcLibLyrics>> {
+        self.request_optional("api/get", Some(query)).await
+    }
+
+    pub async fn find_by_signature(
+        &self,
+        query: &LrcLibSignatureQuery,
+    ) -> Result<Option<LrcLibLyrics>> {
+        if let Some(lyrics) = self.get_cached(query).await? {
+            return Ok(Some(lyrics));
+        }
+        self.get(query).await
+    }
+
+    pub async fn request_challenge(&self) -> Result<LrcLibChallenge> {
+        let mut url = Url::parse(&self.base_url).unwrap();
+        url.set_path("api/request-challenge");
+        println!("[LRCLIB] Fetch '{}'", url);
+        let response = self.client.post(url).send().await?;
+        Ok(response.error_for_status()?.json().await?)
+    }
+
+    pub async fn publish(&self, body: &LrcLibPublishBody) -> Result<()> {
+        let challenge = self.request_challenge().await?;
+        let token = tokio::task::spawn_blocking(move || challenge.solve()).await?;
+        let mut url = Url::parse(&self.base_url).unwrap();
+        url.set_path("api/publish");
+        println!("[LRCLIB] Publish '{}'", url);
+        let response = self
+            .client
+            .post(url)
+            .header("X-Publish-Token", token)
+            .json(body)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            anyhow::bail!("LrcLib publish failed ({}): {}", status, text);
+        }
+        Ok(())
+    }
+}
+
+impl LrcLibLyrics {
+    fn into_content(self) -> String {
+        self.synced_lyrics.or(self.plain_lyrics).unwrap_or_default()
+    }
+
+    /// Instrumental records carry no lyrics, so there is nothing to import from them.
+    fn into_provider_file(self, exact: bool) -> Option<ProviderFile> {
+        if self.instrumental {
+            return None;
+        }
+        let synced = self.synced_lyrics.is_some();
+        let content = self
+            .synced_lyrics
+            .or(self.plain_lyrics)
+            .filter(|content| !content.trim().is_empty())?;
+        Some(ProviderFile {
+            identifier: self.id.to_string(),
+            name: self.name,
+            track_name: self.track_name,
+            album_title: self.album_name,
+            artist_name: self.artist_name,
+            synced,
+            exact,
+            translation: false,
+            duration_ms: Some((self.duration * 1_000.0) as i64),
+            isrc: None,
+            musicbrainz_id: None,
+            content: Some(content),
+        })
+    }
 }
 
 #[async_trait]
@@ -75,6 +238,20 @@ impl LyricsProvider for LrcLibProvider {
     }
 
     async fn search_lyrics(&self, track: &Track) -> Result<Vec<ProviderFile>> {
+        if let Some(query) = LrcLibSignatureQuery::from_track(track) {
+            match self.find_by_signature(&query).await {
+                // An instrumental exact match means there are no lyrics to find
+                Ok(Some(lyrics)) => {
+                    return Ok(lyrics.into_provider_file(true).into_iter().collect())
+                }
+                Ok(None) => {}
+                Err(e) => log_current_job(format!(
+                    "[LRCLIB] Exact lookup failed, falling back to search: {}",
+                    e
+                )),
+            }
+        }
+
         let results = self
             .search(Some(LrcLibLyricsQuery {
                 track_name: Some(track.title.clone()),
@@ -85,21 +262,56 @@ impl LyricsProvider for LrcLibProvider {
             .await?;
         Ok(results
             .into_iter()
-            .map(|r| ProviderFile {
-                identifier: r.id.to_string(),
-                name: r.name,
-                track_name: r.track_name,
-                album_title: r.album_name,
-                artist_name: r.artist_name,
-                synced: r.synced_lyrics.is_some(),
-                duration_ms: Some((r.duration * 1_000.0) as i64),
-                content: Some(r.synced_lyrics.unwrap_or(r.plain_lyrics)),
-            })
+            .filter_map(|r| r.into_provider_file(false))
             .collect())
     }
 
     async fn download(&self, file: &ProviderFile) -> Result<String> {
         let result = self.retrieve(file.identifier.parse()?).await?;
-        Ok(result.synced_lyrics.unwrap_or(result.plain_lyrics))
+        Ok(result.into_content())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lyrics(instrumental: bool, synced_lyrics: Option<&str>) -> LrcLibLyrics {
+        LrcLibLyrics {
+            id: 1,
+            name: "Waves".into(),
+            track_name: "Waves".into(),
+            artist_name: "Tidewater".into(),
+            album_name: "Tidewater".into(),
+            duration: 143.0,
+            instrumental,
+            plain_lyrics: None,
+            synced_lyrics: synced_lyrics.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_skip_instrumental() {
+        assert!(lyrics(true, None).into_provider_file(true).is_none());
+        assert!(lyrics(false, Some("")).into_provider_file(true).is_none());
+
+        let file = lyrics(false, Some("[00:01.00] Hello"))
+            .into_provider_file(true)
+            .unwrap();
+        assert!(file.exact && file.synced);
+        assert_eq!(file.content.as_deref(), Some("[00:01.00] Hello"));
+    }
+
+    #[test]
+    fn test_solve_challenge() {
+        let challenge = LrcLibChallenge {
+            prefix: "singarr".into(),
+            target: "0FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF".into(),
+        };
+        let token = challenge.solve();
+        let (prefix, nonce) = token.split_once(':').unwrap();
+        assert_eq!(prefix, "singarr");
+        let hash = Sha256::digest(format!("{}{}", prefix, nonce));
+        assert_eq!(hash[0] >> 4, 0);
     }
 }
@@ -1 +1,7 @@
+pub mod genius;
+pub mod local;
 pub mod lrclib;
+pub mod musixmatch;
+pub mod netease;
+pub mod script;
+pub mod template;
@@ -0,0 +1,500 @@
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use reqwest::{header, Client, Url};
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::Value;
+use tokio::sync::Mutex;
+
+use crate::{
+    models::{provider::ProviderFile, settings::ProvidersSettings, track::Track},
+    services::settings::SettingsService,
+    utils::lrc::format_time,
+    worker::provider::LyricsProvider,
+};
+
+const APP_ID: &str = "web-desktop-app-v1.0";
+
+#[derive(Deserialize)]
+pub struct MusixmatchResponse {
+    pub message: MusixmatchMessage,
+}
+
+#[derive(Deserialize)]
+pub struct MusixmatchMessage {
+    pub header: MusixmatchHeader,
+    #[serde(default)]
+    pub body: Value,
+}
+
+#[derive(Deserialize)]
+pub struct MusixmatchHeader {
+    pub status_code: i64,
+    pub hint: Option<String>,
+}
+
+impl MusixmatchMessage {
+    pub fn parse<T: DeserializeOwned>(self) -> Option<T> {
+        if self.header.status_code != 200 {
+            return None;
+        }
+        serde_json::from_value(self.body).ok()
+    }
+}
+
+#[derive(Deserialize)]
+pub struct MusixmatchTokenBody {
+    pub user_token: String,
+}
+
+#[derive(Deserialize)]
+pub struct MusixmatchMacroBody {
+    pub macro_calls: HashMap<String, MusixmatchResponse>,
+}
+
+impl MusixmatchMacroBody {
+    fn call<T: DeserializeOwned>(&mut self, name: &str) -> Option<T> {
+        self.macro_calls.remove(name)?.message.parse()
+    }
+}
+
+#[derive(Deserialize)]
+pub struct MusixmatchTrackBody {
+    pub track: MusixmatchTrack,
+}
+
+#[derive(Deserialize)]
+pub struct MusixmatchTrack {
+    pub track_id: i64,
+    pub track_name: String,
+    pub artist_name: String,
+    pub album_name: String,
+    pub track_length: Option<i64>,
+    #[serde(default)]
+    pub track_isrc: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct MusixmatchLyricsBody {
+    pub lyrics: MusixmatchLyrics,
+}
+
+#[derive(Deserialize)]
+pub struct MusixmatchLyrics {
+    pub lyrics_body: String,
+}
+
+#[derive(Deserialize)]
+pub struct MusixmatchSubtitlesBody {
+    pub subtitle_list: Vec<MusixmatchSubtitleItem>,
+}
+
+#[derive(Deserialize)]
+pub struct MusixmatchSubtitleItem {
+    pub subtitle: MusixmatchSubtitle,
+}
+
+#[derive(Deserialize)]
+pub struct MusixmatchSubtitle {
+    pub subtitle_body: String,
+}
+
+#[derive(Deserialize)]
+pub struct MusixmatchSubtitleLine {
+    pub text: String,
+    pub time: MusixmatchSubtitleTime,
+}
+
+#[derive(Deserialize)]
+pub struct MusixmatchSubtitleTime {
+    pub total: f64,
+}
+
+#[derive(Deserialize)]
+pub struct MusixmatchRichsyncBody {
+    pub richsync: MusixmatchRichsync,
+}
+
+#[derive(Deserialize)]
+pub struct MusixmatchRichsync {
+    pub richsync_body: String,
+}
+
+#[derive(Deserialize)]
+pub struct MusixmatchRichsyncLine {
+    pub ts: f64,
+    pub l: Vec<MusixmatchRichsyncWord>,
+}
+
+#[derive(Deserialize)]
+pub struct MusixmatchRichsyncWord {
+    pub c: String,
+    pub o: f64,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum MusixmatchLyricsKind {
+    Richsync,
+    Subtitles,
+    Lyrics,
+}
+
+impl MusixmatchLyricsKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Richsync => "richsync",
+            Self::Subtitles => "subtitles",
+            Self::Lyrics => "lyrics",
+        }
+    }
+
+    fn from_identifier(identifier: &str) -> Result<(i64, Self)> {
+        let (id, kind) = identifier
+            .split_once('/')
+            .ok_or(anyhow!("Invalid Musixmatch identifier"))?;
+        let kind = match kind {
+            "richsync" => Self::Richsync,
+            "subtitles" => Self::Subtitles,
+            "lyrics" => Self::Lyrics,
+            _ => anyhow::bail!("Invalid Musixmatch identifier"),
+        };
+        Ok((id.parse()?, kind))
+    }
+}
+
+fn seconds(value: f64) -> Duration {
+    Duration::from_secs_f64(value.max(0.0))
+}
+
+pub fn subtitles_to_lrc(body: &str) -> Result<String> {
+    let lines: Vec<MusixmatchSubtitleLine> = serde_json::from_str(body)?;
+    Ok(lines
+        .iter()
+        .map(|line| format!("[{}] {}", format_time(seconds(line.time.total)), line.text))
+        .collect::<Vec<String>>()
+        .join("\n"))
+}
+
+pub fn richsync_to_lrc(body: &str) -> Result<String> {
+    let lines: Vec<MusixmatchRichsyncLine> = serde_json::from_str(body)?;
+    Ok(lines
+        .iter()
+        .map(|line| {
+            let mut output = format!("[{}] ", format_time(seconds(line.ts)));
+            for word in &line.l {
+                if !word.c.trim().is_empty() {
+                    output.push_str(&format!("<{}>", format_time(seconds(line.ts + word.o))));
+                }
+                output.push_str(&word.c);
+            }
+            output
+        })
+        .collect::<Vec<String>>()
+        .join("\n"))
+}
+
+impl MusixmatchTrack {
+    fn provider_file(&self, kind: MusixmatchLyricsKind, content: String) -> ProviderFile {
+        ProviderFile {
+            identifier: format!("{}/{}", self.track_id, kind.as_str()),
+            name: format!("{} - {}", self.artist_name, self.track_name),
+            track_name: self.track_name.clone(),
+            artist_name: self.artist_name.clone(),
+            album_title: self.album_name.clone(),
+            synced: kind != MusixmatchLyricsKind::Lyrics,
+            exact: false,
+            translation: false,
+            duration_ms: self.track_length.map(|l| l * 1_000),
+            isrc: self.track_isrc.clone().filter(|isrc| !isrc.is_empty()),
+            musicbrainz_id: None,
+            content: Some(content),
+        }
+    }
+}
+
+impl MusixmatchMacroBody {
+    pub fn into_provider_files(mut self, enhanced: bool) -> Vec<ProviderFile> {
+        let Some(MusixmatchTrackBody { track }) = self.call("matcher.track.get") else {
+            return Vec::new();
+        };
+
+        let richsync = self
+            .call::<MusixmatchRichsyncBody>("track.richsync.get")
+            .and_then(|b| richsync_to_lrc(&b.richsync.richsync_body).ok());
+        let subtitles = self
+            .call::<MusixmatchSubtitlesBody>("track.subtitles.get")
+            .and_then(|b| b.subtitle_list.into_iter().next())
+            .and_then(|s| subtitles_to_lrc(&s.subtitle.subtitle_body).ok());
+        let lyrics = self
+            .call::<MusixmatchLyricsBody>("track.lyrics.get")
+            .map(|b| b.lyrics.lyrics_body)
+            .filter(|body| !body.trim().is_empty());
+
+        let mut files = Vec::new();
+        if let (true, Some(content)) = (enhanced, richsync) {
+            files.push(track.provider_file(MusixmatchLyricsKind::Richsync, content));
+        }
+        if let Some(content) = subtitles {
+            files.push(track.provider_file(MusixmatchLyricsKind::Subtitles, content));
+        }
+        if let Some(content) = lyrics {
+            files.push(track.provider_file(MusixmatchLyricsKind::Lyrics, content));
+        }
+        files
+    }
+}
+
+pub struct MusixmatchProvider {
+    base_url: String,
+    settings_service: Arc<SettingsService>,
+    client: Client,
+    token: Mutex<Option<String>>,
+}
+
+impl MusixmatchProvider {
+    pub fn new(settings_service: Arc<SettingsService>) -> Self {
+        Self {
+            base_url: "https://apic-desktop.musixmatch.com/ws/1.1/".into(),
+            settings_service,
+            client: Client::new(),
+            token: Mutex::new(None),
+        }
+    }
+
+    async fn send(&self, path: &str, params: &[(&str, String)]) -> Result<MusixmatchMessage> {
+        let mut url = Url::parse(&self.base_url)?.join(path)?;
+        url.query_pairs_mut()
+            .append_pair("format", "json")
+            .append_pair("app_id", APP_ID);
+        for (key, value) in params {
+            url.query_pairs_mut().append_pair(key, value);
+        }
+        println!("[MUSIXMATCH] Fetch '{}'", url);
+        let builder = self
+            .client
+            .get(url)
+            .header(header::COOKIE, "AWSELBCORS=0; AWSELB=0");
+        let response = builder.send().await?.error_for_status()?;
+        Ok(response.json::<MusixmatchResponse>().await?.message)
+    }
+
+    async fn fetch_token(&self) -> Result<String> {
+        let message = self.send("token.get", &[]).await?;
+        if message.header.status_code != 200 {
+            anyhow::bail!(
+                "Failed to get Musixmatch token ({}: {})",
+                message.header.status_code,
+                message.header.hint.unwrap_or_default()
+            );
+        }
+        let body: MusixmatchTokenBody = serde_json::from_value(message.body)?;
+        Ok(body.user_token)
+    }
+
+    async fn get_token(&self) -> Result<String> {
+        if let Some(token) = self.settings_service.get().await.providers.musixmatch.token {
+            return Ok(token);
+        }
+        let mut cached = self.token.lock().await;
+        if let Some(token) = cached.as_ref() {
+            return Ok(token.clone());
+        }
+        let token = self.fetch_token().await?;
+        *cached = Some(token.clone());
+        Ok(token)
+    }
+
+    pub async fn request(
+        &self,
+        path: &str,
+        params: &[(&str, String)],
+    ) -> Result<MusixmatchMessage> {
+        let mut renewed = false;
+        loop {
+            let mut params = params.to_vec();
+            params.push(("usertoken", self.get_token().await?));
+            let message = self.send(path, &params).await?;
+            if message.header.status_code == 401 && !renewed {
+                *self.token.lock().await = None;
+                renewed = true;
+                continue;
+            }
+            if message.header.status_code != 200 {
+                anyhow::bail!(
+                    "Musixmatch request failed ({}: {})",
+                    message.header.status_code,
+                    message.header.hint.unwrap_or_default()
+                );
+            }
+            return Ok(message);
+        }
+    }
+
+    pub async fn match_track(&self, track: &Track, enhanced: bool) -> Result<MusixmatchMacroBody> {
+        let mut params = vec![
+            ("namespace", "lyrics_richsynched".into()),
+            ("subtitle_format", "mxm".into()),
+            ("q_track", track.title.clone()),
+            ("q_artist", track.artist.name.clone()),
+            ("q_album", track.album.album.title.clone()),
+        ];
+        if track.duration_ms > 0 {
+            params.push(("q_duration", (track.duration_ms / 1_000).to_string()));
+        }
+        if let Some(isrc) = &track.isrc {
+            params.push(("track_isrc", isrc.clone()));
+        }
+        if enhanced {
+            params.push(("optional_calls", "track.richsync".into()));
+        }
+        let message = self.request("macro.subtitles.get", &params).await?;
+        Ok(serde_json::from_value(message.body)?)
+    }
+
+    pub async fn retrieve(&self, track_id: i64, kind: MusixmatchLyricsKind) -> Result<String> {
+        let track_id = ("track_id", track_id.to_string());
+        match kind {
+            MusixmatchLyricsKind::Richsync => {
+                let message = self.request("track.richsync.get", &[track_id]).await?;
+                let body: MusixmatchRichsyncBody = serde_json::from_value(message.body)?;
+                richsync_to_lrc(&body.richsync.richsync_body)
+            }
+            MusixmatchLyricsKind::Subtitles => {
+                let params = [track_id, ("subtitle_format", "mxm".into())];
+                let message = self.request("track.subtitles.get", &params).await?;
+                let body: MusixmatchSubtitlesBody = serde_json::from_value(message.body)?;
+                let subtitle = body
+                    .subtitle_list
+                    .into_iter()
+                    .next()
+                    .ok_or(anyhow!("No subtitles found on Musixmatch"))?;
+                subtitles_to_lrc(&subtitle.subtitle.subtitle_body)
+            }
+            MusixmatchLyricsKind::Lyrics => {
+                let message = self.request("track.lyrics.get", &[track_id]).await?;
+                let body: MusixmatchLyricsBody = serde_json::from_value(message.body)?;
+                Ok(body.lyrics.lyrics_body)
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl LyricsProvider for MusixmatchProvider {
+    fn name(&self) -> &'static str {
+        "Musixmatch"
+    }
+
+    fn enabled(&self, settings: &ProvidersSettings) -> bool {
+        settings.musixmatch.enabled
+    }
+
+    fn cache_scope(&self, settings: &ProvidersSettings) -> String {
+        format!("enhanced={}", settings.musixmatch.enhanced)
+    }
+
+    async fn search_lyrics(&self, track: &Track) -> Result<Vec<ProviderFile>> {
+        let settings = self.settings_service.get().await.providers.musixmatch;
+        if !settings.enabled {
+            return Ok(Vec::new());
+        }
+        let body = self.match_track(track, settings.enhanced).await?;
+        Ok(body.into_provider_files(settings.enhanced))
+    }
+
+    async fn download(&self, file: &ProviderFile) -> Result<String> {
+        let (track_id, kind) = MusixmatchLyricsKind::from_identifier(&file.identifier)?;
+        self.retrieve(track_id, kind).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(name: &str) -> MusixmatchMessage {
+        let path = format!(
+            "{}/tests/fixtures/musixmatch/{}",
+            env!("CARGO_MANIFEST_DIR"),
+            name
+        );
+        let response: MusixmatchResponse =
+            serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        response.message
+    }
+
+    #[test]
+    fn test_token() {
+        let body: MusixmatchTokenBody = fixture("token.json").parse().unwrap();
+        assert_eq!(
+            body.user_token,
+            "2501192ac605cc2e16b6b2c04fe43d1011a38d919fe802976084e7"
+        );
+    }
+
+    #[test]
+    fn test_token_captcha() {
+        let message = fixture("token_captcha.json");
+        assert_eq!(message.header.status_code, 401);
+        assert_eq!(message.header.hint.as_deref(), Some("captcha"));
+        assert!(message.parse::<MusixmatchTokenBody>().is_none());
+    }
+
+    #[test]
+    fn test_macro() {
+        let body: MusixmatchMacroBody = fixture("macro.json").parse().unwrap();
+        let files = body.into_provider_files(true);
+        assert_eq!(files.len(), 3);
+
+        assert_eq!(files[0].identifier, "84584600/richsync");
+        assert_eq!(files[0].name, "Harbour Lights - Almost Home");
+        assert_eq!(files[0].album_title, "Tidewater");
+        assert_eq!(files[0].duration_ms, Some(214_000));
+        assert_eq!(files[0].isrc.as_deref(), Some("GBAYE0601498"));
+        assert!(files[0].synced);
+        assert_eq!(
+            files[0].content.as_deref(),
+            Some(
+                "[00:12.34] <00:12.34>Morning <00:12.84>light\n\
+                [00:15.10] <00:15.10>Gulls <00:15.60>are <00:15.85>singing"
+            )
+        );
+
+        assert_eq!(files[1].identifier, "84584600/subtitles");
+        assert!(files[1].synced);
+        assert_eq!(
+            files[1].content.as_deref(),
+            Some("[00:12.34] Morning light\n[00:15.10] Gulls are singing\n[00:18.00] ")
+        );
+
+        assert_eq!(files[2].identifier, "84584600/lyrics");
+        assert!(!files[2].synced);
+        assert_eq!(
+            files[2].content.as_deref(),
+            Some("Morning light\nGulls are singing")
+        );
+    }
+
+    #[test]
+    fn test_macro_without_enhanced() {
+        let body: MusixmatchMacroBody = fixture("macro.json").parse().unwrap();
+        let files = body.into_provider_files(false);
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].identifier, "84584600/subtitles");
+    }
+
+    #[test]
+    fn test_macro_not_found() {
+        let body: MusixmatchMacroBody = fixture("macro_not_found.json").parse().unwrap();
+        assert!(body.into_provider_files(true).is_empty());
+    }
+
+    #[test]
+    fn test_identifier() {
+        let (id, kind) = MusixmatchLyricsKind::from_identifier("84584600/subtitles").unwrap();
+        assert_eq!(id, 84584600);
+        assert!(kind == MusixmatchLyricsKind::Subtitles);
+        assert!(MusixmatchLyricsKind::from_identifier("84584600").is_err());
+    }
+}
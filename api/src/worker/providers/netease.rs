@@ -0,0 +1,327 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use futures::future::join_all;
+use reqwest::{header, Client, Url};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{
+    models::{provider::ProviderFile, settings::ProvidersSettings, track::Track},
+    worker::provider::{partial_results, LyricsProvider},
+};
+
+const MAX_SONGS: usize = 5;
+const TRANSLATION_SUFFIX: &str = "/tlyric";
+
+#[derive(Serialize)]
+pub struct NeteaseSearchQuery {
+    pub s: String,
+    #[serde(rename = "type")]
+    pub search_type: i64,
+    pub limit: i64,
+    pub offset: i64,
+}
+
+#[derive(Serialize)]
+pub struct NeteaseLyricQuery {
+    pub id: i64,
+    pub lv: i64,
+    pub tv: i64,
+}
+
+#[derive(Deserialize)]
+pub struct NeteaseSearchResponse {
+    pub result: Option<NeteaseSearchResult>,
+}
+
+#[derive(Deserialize)]
+pub struct NeteaseSearchResult {
+    #[serde(default)]
+    pub songs: Vec<NeteaseSong>,
+}
+
+#[derive(Deserialize)]
+pub struct NeteaseSong {
+    pub id: i64,
+    pub name: String,
+    pub artists: Vec<NeteaseArtist>,
+    pub album: NeteaseAlbum,
+    pub duration: Option<i64>,
+}
+
+#[derive(Deserialize)]
+pub struct NeteaseArtist {
+    pub name: String,
+}
+
+#[derive(Deserialize)]
+pub struct NeteaseAlbum {
+    pub name: String,
+}
+
+#[derive(Deserialize)]
+pub struct NeteaseLyricResponse {
+    pub lrc: Option<NeteaseLyric>,
+    pub tlyric: Option<NeteaseLyric>,
+}
+
+#[derive(Deserialize)]
+pub struct NeteaseLyric {
+    pub lyric: Option<String>,
+}
+
+impl NeteaseLyric {
+    fn text(&self) -> Option<String> {
+        let lyric = self.lyric.as_ref()?;
+        let text = lyric
+            .lines()
+            .filter(|line| !line.trim_start().starts_with('{'))
+            .collect::<Vec<&str>>()
+            .join("\n");
+        let text = text.trim();
+        if text.is_empty() {
+            None
+        } else {
+            Some(text.into())
+        }
+    }
+}
+
+fn is_synced(text: &str) -> bool {
+    text.lines().any(|line| {
+        let mut chars = line.trim_start().chars();
+        chars.next() == Some('[') && chars.next().is_some_and(|c| c.is_ascii_digit())
+    })
+}
+
+impl NeteaseSong {
+    fn artist_name(&self) -> String {
+        self.artists
+            .iter()
+            .map(|a| a.name.as_str())
+            .collect::<Vec<&str>>()
+            .join(", ")
+    }
+
+    fn provider_file(
+        &self,
+        identifier: String,
+        name: String,
+        content: String,
+        translation: bool,
+    ) -> ProviderFile {
+        ProviderFile {
+            identifier,
+            name,
+            track_name: self.name.clone(),
+            artist_name: self.artist_name(),
+            album_title: self.album.name.clone(),
+            synced: is_synced(&content),
+            exact: false,
+            translation,
+            duration_ms: self.duration,
+            isrc: None,
+            musicbrainz_id: None,
+            content: Some(content),
+        }
+    }
+
+    pub fn into_provider_files(self, lyrics: NeteaseLyricResponse) -> Vec<ProviderFile> {
+        let mut files = Vec::new();
+        let name = format!("{} - {}", self.artist_name(), self.name);
+        if let Some(content) = lyrics.lrc.as_ref().and_then(NeteaseLyric::text) {
+            files.push(self.provider_file(self.id.to_string(), name.clone(), content, false));
+        }
+        if let Some(content) = lyrics.tlyric.as_ref().and_then(NeteaseLyric::text) {
+            files.push(self.provider_file(
+                format!("{}{}", self.id, TRANSLATION_SUFFIX),
+                format!("{} (Translation)", name),
+                content,
+                true,
+            ));
+        }
+        files
+    }
+}
+
+pub struct NeteaseProvider {
+    base_url: String,
+    client: Client,
+}
+
+impl Default for NeteaseProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NeteaseProvider {
+    pub fn new() -> Self {
+        Self {
+            base_url: "https://music.163.com/".into(),
+            client: Client::new(),
+        }
+    }
+
+    pub async fn request<T: DeserializeOwned, Q: Serialize>(
+        &self,
+        path: &str,
+        query: Option<Q>,
+    ) -> Result<T> {
+        let mut url = Url::parse(&self.base_url).unwrap();
+        url.set_path(path);
+        if let Some(q) = query {
+            let query_string = serde_qs::to_string(&q)?;
+            url.set_query(Some(&query_string));
+        }
+        println!("[NETEASE] Fetch '{}'", url);
+        let builder = self
+            .client
+            .get(url)
+            .header(header::REFERER, self.base_url.as_str());
+        Ok(builder
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<T>()
+            .await?)
+    }
+
+    pub async fn search(&self, s: String) -> Result<Vec<NeteaseSong>> {
+        let response: NeteaseSearchResponse = self
+            .request(
+                "api/search/get/web",
+                Some(NeteaseSearchQuery {
+                    s,
+                    search_type: 1,
+                    limit: MAX_SONGS as i64,
+                    offset: 0,
+                }),
+            )
+            .await?;
+        Ok(response.result.map(|r| r.songs).unwrap_or_default())
+    }
+
+    pub async fn lyric(&self, id: i64) -> Result<NeteaseLyricResponse> {
+        self.request(
+            "api/song/lyric",
+            Some(NeteaseLyricQuery { id, lv: -1, tv: -1 }),
+        )
+        .await
+    }
+
+    async fn song_files(&self, song: NeteaseSong) -> Result<Vec<ProviderFile>> {
+        let lyrics = self.lyric(song.id).await?;
+        Ok(song.into_provider_files(lyrics))
+    }
+}
+
+#[async_trait]
+impl LyricsProvider for NeteaseProvider {
+    fn name(&self) -> &'static str {
+        "NetEase"
+    }
+
+    fn enabled(&self, settings: &ProvidersSettings) -> bool {
+        settings.netease.enabled
+    }
+
+    async fn search_lyrics(&self, track: &Track) -> Result<Vec<ProviderFile>> {
+        let songs = self
+            .search(format!("{} {}", track.title, track.artist.name))
+            .await?;
+
+        let files = join_all(songs.into_iter().map(|song| self.song_files(song))).await;
+
+        Ok(partial_results(self.name(), files)?
+            .into_iter()
+            .flatten()
+            .collect())
+    }
+
+    async fn download(&self, file: &ProviderFile) -> Result<String> {
+        let (id, translation) = match file.identifier.strip_suffix(TRANSLATION_SUFFIX) {
+            Some(id) => (id, true),
+            None => (file.identifier.as_str(), false),
+        };
+        let lyrics = self.lyric(id.parse()?).await?;
+        let lyric = if translation {
+            lyrics.tlyric
+        } else {
+            lyrics.lrc
+        };
+        lyric
+            .as_ref()
+            .and_then(NeteaseLyric::text)
+            .ok_or(anyhow!("No lyrics found on NetEase"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture<T: DeserializeOwned>(name: &str) -> T {
+        let path = format!(
+            "{}/tests/fixtures/netease/{}",
+            env!("CARGO_MANIFEST_DIR"),
+            name
+        );
+        serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_search() {
+        let response: NeteaseSearchResponse = fixture("search.json");
+        let songs = response.result.unwrap().songs;
+        assert_eq!(songs.len(), 2);
+        assert_eq!(songs[0].id, 1901371647);
+        assert_eq!(songs[0].name, "晴天");
+        assert_eq!(songs[0].artist_name(), "周杰伦");
+        assert_eq!(songs[0].album.name, "叶惠美");
+        assert_eq!(songs[0].duration, Some(269000));
+        assert_eq!(songs[1].artist_name(), "YOASOBI, Ayase");
+    }
+
+    #[test]
+    fn test_lyric_with_translation() {
+        let response: NeteaseSearchResponse = fixture("search.json");
+        let song = response.result.unwrap().songs.remove(1);
+        let files = song.into_provider_files(fixture("lyric_translated.json"));
+        assert_eq!(files.len(), 2);
+
+        assert_eq!(files[0].identifier, "1409311773");
+        assert_eq!(files[0].name, "YOASOBI, Ayase - 夜に駆ける");
+        assert!(files[0].synced);
+        assert_eq!(
+            files[0].content.as_deref(),
+            Some("[00:00.00] 静かな夜に歌う\n[00:04.21] 星が流れてゆく")
+        );
+
+        assert_eq!(files[1].identifier, "1409311773/tlyric");
+        assert_eq!(files[1].name, "YOASOBI, Ayase - 夜に駆ける (Translation)");
+        assert!(files[1].synced);
+        assert_eq!(
+            files[1].content.as_deref(),
+            Some("[by:translator]\n[00:00.00]在安静的夜里歌唱\n[00:04.21]星星流过")
+        );
+    }
+
+    #[test]
+    fn test_lyric_without_translation() {
+        let response: NeteaseSearchResponse = fixture("search.json");
+        let song = response.result.unwrap().songs.remove(0);
+        let files = song.into_provider_files(fixture("lyric.json"));
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].identifier, "1901371647");
+        assert_eq!(files[0].duration_ms, Some(269000));
+        assert!(files[0].synced);
+    }
+
+    #[test]
+    fn test_lyric_missing() {
+        let response: NeteaseSearchResponse = fixture("search.json");
+        let song = response.result.unwrap().songs.remove(0);
+        let files = song.into_provider_files(fixture("lyric_missing.json"));
+        assert!(files.is_empty());
+    }
+}
@@ -0,0 +1,228 @@
+use std::{process::Stdio, sync::Arc, time::Duration};
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use serde::{de::DeserializeOwned, Serialize};
+use tokio::{io::AsyncWriteExt, process::Command};
+
+use crate::{
+    models::{
+        job::log_current_job, provider::ProviderFile, settings::ProvidersSettings, track::Track,
+    },
+    services::settings::SettingsService,
+    worker::provider::LyricsProvider,
+};
+
+pub struct ScriptOutput {
+    pub stdout: String,
+    pub stderr: String,
+}
+
+pub struct ScriptRunner {
+    pub path: String,
+    pub timeout: Duration,
+}
+
+impl ScriptRunner {
+    pub async fn run<I: Serialize>(&self, command: &str, input: &I) -> Result<ScriptOutput> {
+        let input = serde_json::to_vec(input)?;
+        println!("[SCRIPT] Run '{} {}'", self.path, command);
+
+        let mut child = Command::new(&self.path)
+            .arg(command)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()?;
+
+        let mut stdin = child
+            .stdin
+            .take()
+            .ok_or(anyhow!("Failed to open script stdin"))?;
+        let write = async move {
+            let _ = stdin.write_all(&input).await;
+        };
+
+        let (_, output) = tokio::time::timeout(
+            self.timeout,
+            futures::future::join(write, child.wait_with_output()),
+        )
+        .await
+        .map_err(|_| {
+            anyhow!(
+                "Script '{}' timed out after {}s",
+                self.path,
+                self.timeout.as_secs()
+            )
+        })?;
+        let output = output?;
+
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        for line in stderr.lines().filter(|line| !line.trim().is_empty()) {
+            log_current_job(format!("[SCRIPT] {}", line));
+        }
+
+        if !output.status.success() {
+            return Err(anyhow!(
+                "Script '{}' exited with {}: {}",
+                self.path,
+                output.status,
+                stderr.trim()
+            ));
+        }
+
+        Ok(ScriptOutput {
+            stdout: String::from_utf8(output.stdout)?,
+            stderr,
+        })
+    }
+
+    pub async fn run_json<I: Serialize, T: DeserializeOwned>(
+        &self,
+        command: &str,
+        input: &I,
+    ) -> Result<T> {
+        let output = self.run(command, input).await?;
+        serde_json::from_str(&output.stdout)
+            .map_err(|e| anyhow!("Invalid output from script '{}': {}", self.path, e))
+    }
+
+    pub async fn search(&self, track: &Track) -> Result<Vec<ProviderFile>> {
+        self.run_json("search", track).await
+    }
+
+    pub async fn download(&self, file: &ProviderFile) -> Result<String> {
+        let file: ProviderFile = self.run_json("download", file).await?;
+        file.content
+            .ok_or(anyhow!("Script '{}' returned no content", self.path))
+    }
+}
+
+pub struct ScriptProvider {
+    settings_service: Arc<SettingsService>,
+}
+
+impl ScriptProvider {
+    pub fn new(settings_service: Arc<SettingsService>) -> Self {
+        Self { settings_service }
+    }
+
+    async fn runner(&self) -> Option<ScriptRunner> {
+        let settings = self.settings_service.get().await.providers.script;
+        if !settings.enabled || settings.path.is_empty() {
+            return None;
+        }
+        Some(ScriptRunner {
+            path: settings.path,
+            timeout: Duration::from_secs(settings.timeout.max(1)),
+        })
+    }
+}
+
+#[async_trait]
+impl LyricsProvider for ScriptProvider {
+    fn name(&self) -> &'static str {
+        "Script"
+    }
+
+    fn enabled(&self, settings: &ProvidersSettings) -> bool {
+        settings.script.enabled && !settings.script.path.is_empty()
+    }
+
+    fn cache_scope(&self, settings: &ProvidersSettings) -> String {
+        settings.script.path.clone()
+    }
+
+    async fn search_lyrics(&self, track: &Track) -> Result<Vec<ProviderFile>> {
+        match self.runner().await {
+            Some(runner) => runner.search(track).await,
+            None => Ok(Vec::new()),
+        }
+    }
+
+    async fn download(&self, file: &ProviderFile) -> Result<String> {
+        self.runner()
+            .await
+            .ok_or(anyhow!("Script provider is not configured"))?
+            .download(file)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::fixtures::track;
+
+    fn runner(timeout: u64) -> ScriptRunner {
+        ScriptRunner {
+            path: format!(
+                "{}/tests/fixtures/script/provider.sh",
+                env!("CARGO_MANIFEST_DIR")
+            ),
+            timeout: Duration::from_secs(timeout),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_search() {
+        let files = runner(5)
+            .search(&track("Almost Home", "Harbour Lights"))
+            .await
+            .unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].identifier, "almost-home");
+        assert_eq!(files[0].track_name, "Almost Home");
+        assert_eq!(files[0].duration_ms, Some(143_000));
+        assert!(files[0].synced);
+        assert!(!files[0].exact);
+        assert_eq!(files[0].content, None);
+
+        let files = runner(5)
+            .search(&track("Something Else", "Harbour Lights"))
+            .await
+            .unwrap();
+        assert!(files.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_download() {
+        let files = runner(5)
+            .search(&track("Almost Home", "Harbour Lights"))
+            .await
+            .unwrap();
+        let content = runner(5).download(&files[0]).await.unwrap();
+        assert_eq!(
+            content,
+            "[00:01.00]Hold on, hold on\n[00:04.50]We're almost home"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stderr() {
+        let output = runner(5)
+            .run("search", &track("Almost Home", "Harbour Lights"))
+            .await
+            .unwrap();
+        assert_eq!(output.stderr, "searching for Almost Home\n");
+    }
+
+    #[tokio::test]
+    async fn test_failure() {
+        let error = runner(5)
+            .search(&track("Broken", "Harbour Lights"))
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("lyrics source unavailable"));
+    }
+
+    #[tokio::test]
+    async fn test_timeout() {
+        let error = runner(1)
+            .search(&track("Slow", "Harbour Lights"))
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("timed out"));
+    }
+}
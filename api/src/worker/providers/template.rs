@@ -0,0 +1,394 @@
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use reqwest::Client;
+use serde_json::Value;
+
+use crate::{
+    models::{
+        job::log_current_job,
+        provider::ProviderFile,
+        settings::{
+            ProvidersSettings, TemplateDurationUnit, TemplateFieldsSettings, TemplateSettings,
+        },
+        track::Track,
+    },
+    services::settings::SettingsService,
+    worker::provider::LyricsProvider,
+};
+
+pub fn render_template(template: &str, variables: &[(&str, String)]) -> String {
+    let mut output = template.to_string();
+    for (key, value) in variables {
+        let encoded = utf8_percent_encode(value, NON_ALPHANUMERIC).to_string();
+        output = output.replace(&format!("{{{}}}", key), &encoded);
+    }
+    output
+}
+
+pub fn track_variables(track: &Track) -> Vec<(&'static str, String)> {
+    vec![
+        ("title", track.title.clone()),
+        ("artist", track.artist.name.clone()),
+        ("album", track.album.album.title.clone()),
+        (
+            "duration",
+            ((track.duration_ms as f64 / 1_000.0).round() as i64).to_string(),
+        ),
+        ("duration_ms", track.duration_ms.to_string()),
+        (
+            "mbid",
+            track.recording_musicbrainz_id.clone().unwrap_or_default(),
+        ),
+        ("isrc", track.isrc.clone().unwrap_or_default()),
+        (
+            "artist_mbid",
+            track.artist.musicbrainz_id.clone().unwrap_or_default(),
+        ),
+        (
+            "album_mbid",
+            track.album.album.musicbrainz_id.clone().unwrap_or_default(),
+        ),
+    ]
+}
+
+pub fn file_variables(file: &ProviderFile) -> Vec<(&'static str, String)> {
+    vec![
+        ("identifier", file.identifier.clone()),
+        ("title", file.track_name.clone()),
+        ("artist", file.artist_name.clone()),
+        ("album", file.album_title.clone()),
+    ]
+}
+
+fn pointer<'a>(value: &'a Value, pointer: &str) -> Option<&'a Value> {
+    if pointer.is_empty() {
+        return None;
+    }
+    value.pointer(pointer).filter(|v| !v.is_null())
+}
+
+fn pointer_string(value: &Value, pointer_str: &str) -> Option<String> {
+    match pointer(value, pointer_str)? {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+fn pointer_text(value: &Value, pointer_str: &str) -> Option<String> {
+    pointer_string(value, pointer_str).filter(|s| !s.trim().is_empty())
+}
+
+fn pointer_duration_ms(
+    value: &Value,
+    pointer_str: &str,
+    unit: TemplateDurationUnit,
+) -> Option<i64> {
+    let duration = match pointer(value, pointer_str)? {
+        Value::Number(n) => n.as_f64()?,
+        Value::String(s) => s.trim().parse().ok()?,
+        _ => return None,
+    };
+    Some(match unit {
+        TemplateDurationUnit::Seconds => (duration * 1_000.0) as i64,
+        TemplateDurationUnit::Milliseconds => duration as i64,
+    })
+}
+
+pub fn extract_content(value: &Value, fields: &TemplateFieldsSettings) -> Option<(String, bool)> {
+    if let Some(synced) = pointer_text(value, &fields.synced_lyrics) {
+        return Some((synced, true));
+    }
+    pointer_text(value, &fields.plain_lyrics).map(|plain| (plain, false))
+}
+
+pub fn extract_files(response: &Value, settings: &TemplateSettings) -> Result<Vec<ProviderFile>> {
+    let items = match response.pointer(&settings.results_pointer) {
+        Some(Value::Array(items)) => items.iter().collect(),
+        Some(Value::Null) | None => Vec::new(),
+        Some(item) => vec![item],
+    };
+
+    let fields = &settings.fields;
+    let mut files = Vec::new();
+    let mut malformed = 0;
+    for item in items {
+        let Some(identifier) = pointer_string(item, &fields.identifier) else {
+            malformed += 1;
+            continue;
+        };
+        let track_name = pointer_string(item, &fields.track_name).unwrap_or_default();
+        let artist_name = pointer_string(item, &fields.artist_name).unwrap_or_default();
+        let content = extract_content(item, fields);
+        if content.is_none() && settings.download_url.is_empty() {
+            continue;
+        }
+        files.push(ProviderFile {
+            identifier,
+            name: pointer_string(item, &fields.name)
+                .unwrap_or_else(|| format!("{} - {}", artist_name, track_name)),
+            album_title: pointer_string(item, &fields.album_title).unwrap_or_default(),
+            duration_ms: pointer_duration_ms(item, &fields.duration, fields.duration_unit),
+            synced: content.as_ref().is_some_and(|(_, synced)| *synced),
+            exact: false,
+            translation: false,
+            isrc: pointer_text(item, &fields.isrc),
+            musicbrainz_id: pointer_text(item, &fields.musicbrainz_id),
+            content: content.map(|(content, _)| content),
+            track_name,
+            artist_name,
+        });
+    }
+    if malformed > 0 {
+        // Every item failing points at the settings rather than at the response
+        if files.is_empty() {
+            return Err(anyhow!("Missing identifier at '{}'", fields.identifier));
+        }
+        log_current_job(format!(
+            "[TEMPLATE] Skipped {} results without an identifier at '{}'",
+            malformed, fields.identifier
+        ));
+    }
+    Ok(files)
+}
+
+pub struct TemplateProvider {
+    settings_service: Arc<SettingsService>,
+    client: Client,
+}
+
+impl TemplateProvider {
+    pub fn new(settings_service: Arc<SettingsService>) -> Self {
+        Self {
+            settings_service,
+            client: Client::new(),
+        }
+    }
+
+    pub async fn fetch(&self, url: &str) -> Result<Value> {
+        println!("[TEMPLATE] Fetch '{}'", url);
+        let response = self.client.get(url).send().await?.error_for_status()?;
+        Ok(response.json::<Value>().await?)
+    }
+
+    pub async fn search(
+        &self,
+        settings: &TemplateSettings,
+        track: &Track,
+    ) -> Result<Vec<ProviderFile>> {
+        let url = render_template(&settings.search_url, &track_variables(track));
+        let response = self.fetch(&url).await?;
+        extract_files(&response, settings)
+    }
+
+    pub async fn retrieve(
+        &self,
+        settings: &TemplateSettings,
+        file: &ProviderFile,
+    ) -> Result<String> {
+        if settings.download_url.is_empty() {
+            return Err(anyhow!("Template provider has no download URL"));
+        }
+        let url = render_template(&settings.download_url, &file_variables(file));
+        let response = self.fetch(&url).await?;
+        extract_content(&response, &settings.fields)
+            .map(|(content, _)| content)
+            .ok_or(anyhow!("No lyrics found in template provider response"))
+    }
+}
+
+#[async_trait]
+impl LyricsProvider for TemplateProvider {
+    fn name(&self) -> &'static str {
+        "Template"
+    }
+
+    fn enabled(&self, settings: &ProvidersSettings) -> bool {
+        settings.template.enabled && !settings.template.search_url.is_empty()
+    }
+
+    fn cache_scope(&self, settings: &ProvidersSettings) -> String {
+        serde_json::to_string(&settings.template).unwrap_or_default()
+    }
+
+    async fn search_lyrics(&self, track: &Track) -> Result<Vec<ProviderFile>> {
+        let settings = self.settings_service.get().await.providers.template;
+        if !settings.enabled || settings.search_url.is_empty() {
+            return Ok(Vec::new());
+        }
+        self.search(&settings, track).await
+    }
+
+    async fn download(&self, file: &ProviderFile) -> Result<String> {
+        let settings = self.settings_service.get().await.providers.template;
+        self.retrieve(&settings, file).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::fixtures;
+    use axum::{
+        extract::{Path, Query},
+        routing::get,
+        Json, Router,
+    };
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    fn track() -> Track {
+        let mut track = fixtures::track("Almost Home", "Harbour Lights");
+        track.duration_ms = 143_400;
+        track.album.album.title = "Tidewater & Co".into();
+        track.artist.musicbrainz_id = Some("a1b2".into());
+        track
+    }
+
+    fn settings(base_url: &str) -> TemplateSettings {
+        TemplateSettings {
+            enabled: true,
+            search_url: format!(
+                "{}/search?q={{title}}&artist={{artist}}&d={{duration}}",
+                base_url
+            ),
+            results_pointer: "/data/results".into(),
+            fields: TemplateFieldsSettings {
+                identifier: "/id".into(),
+                name: "".into(),
+                track_name: "/song/title".into(),
+                artist_name: "/song/artist".into(),
+                album_title: "/song/album".into(),
+                duration: "/length".into(),
+                duration_unit: TemplateDurationUnit::Seconds,
+                isrc: "/isrc".into(),
+                musicbrainz_id: "".into(),
+                synced_lyrics: "/lyrics/synced".into(),
+                plain_lyrics: "/lyrics/plain".into(),
+            },
+            download_url: format!("{}/lyrics/{{identifier}}", base_url),
+        }
+    }
+
+    async fn mock_server() -> String {
+        let app = Router::new()
+            .route(
+                "/search",
+                get(|Query(query): Query<HashMap<String, String>>| async move {
+                    if query.get("q").map(String::as_str) != Some("Almost Home")
+                        || query.get("artist").map(String::as_str) != Some("Harbour Lights")
+                        || query.get("d").map(String::as_str) != Some("143")
+                    {
+                        return Json(json!({ "data": { "results": [] } }));
+                    }
+                    Json(json!({
+                        "data": {
+                            "results": [
+                                {
+                                    "id": 42,
+                                    "isrc": "GBAYE1100001",
+                                    "song": { "title": "Almost Home", "artist": "Harbour Lights", "album": "Tidewater" },
+                                    "length": 143.2,
+                                    "lyrics": { "synced": "[00:01.00]Hold on", "plain": "Hold on" }
+                                },
+                                {
+                                    "id": "b-7",
+                                    "song": { "title": "Almost Home (Live)", "artist": "Harbour Lights" },
+                                    "lyrics": { "synced": null, "plain": "" }
+                                }
+                            ]
+                        }
+                    }))
+                }),
+            )
+            .route(
+                "/lyrics/:id",
+                get(|Path(id): Path<String>| async move {
+                    Json(json!({ "id": id, "lyrics": { "plain": "Hold on, live" } }))
+                }),
+            );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{}", address)
+    }
+
+    #[test]
+    fn test_render_template() {
+        let url = render_template(
+            "https://example.com/{artist}/{album}?d={duration}&mbid={artist_mbid}&x={unknown}",
+            &track_variables(&track()),
+        );
+        assert_eq!(
+            url,
+            "https://example.com/Harbour%20Lights/Tidewater%20%26%20Co?d=143&mbid=a1b2&x={unknown}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_search_and_download() {
+        let base_url = mock_server().await;
+        let settings = settings(&base_url);
+        let provider = TemplateProvider::new(Arc::new(
+            SettingsService::from_path("/nonexistent").await.unwrap(),
+        ));
+
+        let files = provider.search(&settings, &track()).await.unwrap();
+        assert_eq!(files.len(), 2);
+
+        assert_eq!(files[0].identifier, "42");
+        assert_eq!(files[0].name, "Harbour Lights - Almost Home");
+        assert_eq!(files[0].album_title, "Tidewater");
+        assert_eq!(files[0].duration_ms, Some(143_200));
+        assert_eq!(files[0].isrc.as_deref(), Some("GBAYE1100001"));
+        assert!(files[0].synced);
+        assert_eq!(files[0].content.as_deref(), Some("[00:01.00]Hold on"));
+
+        assert_eq!(files[1].identifier, "b-7");
+        assert_eq!(files[1].album_title, "");
+        assert_eq!(files[1].duration_ms, None);
+        assert_eq!(files[1].isrc, None);
+        assert!(!files[1].synced);
+        assert_eq!(files[1].content, None);
+
+        let content = provider.retrieve(&settings, &files[1]).await.unwrap();
+        assert_eq!(content, "Hold on, live");
+    }
+
+    #[tokio::test]
+    async fn test_search_without_download_url() {
+        let base_url = mock_server().await;
+        let mut settings = settings(&base_url);
+        settings.download_url = "".into();
+        let provider = TemplateProvider::new(Arc::new(
+            SettingsService::from_path("/nonexistent").await.unwrap(),
+        ));
+
+        let files = provider.search(&settings, &track()).await.unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].identifier, "42");
+    }
+
+    #[test]
+    fn test_extract_skips_malformed() {
+        let settings = settings("http://localhost");
+        let response = json!({
+            "data": {
+                "results": [
+                    { "song": { "title": "Almost Home" }, "lyrics": { "plain": "Hold on" } },
+                    { "id": 42, "song": { "title": "Almost Home" }, "lyrics": { "plain": "Hold on" } }
+                ]
+            }
+        });
+        let files = extract_files(&response, &settings).unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].identifier, "42");
+
+        let response = json!({ "data": { "results": [{ "song": { "title": "Almost Home" } }] } });
+        assert!(extract_files(&response, &settings).is_err());
+    }
+}
@@ -0,0 +1,38 @@
+#!/bin/sh
+input=$(cat)
+
+case "$1" in
+search)
+    case "$input" in
+    *'"title":"Almost Home"'*)
+        echo "searching for Almost Home" >&2
+        echo '[{"identifier":"almost-home","name":"Harbour Lights - Almost Home","trackName":"Almost Home","artistName":"Harbour Lights","albumTitle":"Tidewater","synced":true,"durationMs":143000}]'
+        ;;
+    *'"title":"Broken"'*)
+        echo "lyrics source unavailable" >&2
+        exit 1
+        ;;
+    *'"title":"Slow"'*)
+        exec sleep 10
+        ;;
+    *)
+        echo '[]'
+        ;;
+    esac
+    ;;
+download)
+    case "$input" in
+    *'"identifier":"almost-home"'*)
+        printf '%s\n' '{"identifier":"almost-home","name":"Harbour Lights - Almost Home","trackName":"Almost Home","artistName":"Harbour Lights","albumTitle":"Tidewater","synced":true,"content":"[00:01.00]Hold on, hold on\n[00:04.50]We'"'"'re almost home"}'
+        ;;
+    *)
+        echo "unknown identifier" >&2
+        exit 1
+        ;;
+    esac
+    ;;
+*)
+    echo "unknown command: $1" >&2
+    exit 2
+    ;;
+esac
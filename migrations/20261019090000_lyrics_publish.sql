@@ -0,0 +1,3 @@
+ALTER TABLE Lyrics ADD COLUMN "published_at" DATETIME;
+
+ALTER TABLE Lyrics ADD COLUMN "publish_error" TEXT;
@@ -0,0 +1,12 @@
+CREATE TABLE provider_cache (
+  "provider" TEXT NOT NULL,
+  "key" TEXT NOT NULL,
+  "created_at" DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  "expires_at" DATETIME NOT NULL,
+
+  "value" TEXT NOT NULL, -- JSON
+
+  PRIMARY KEY ("provider", "key")
+);
+
+CREATE INDEX idx_provider_cache_expires_at ON provider_cache(expires_at);
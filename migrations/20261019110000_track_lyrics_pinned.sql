@@ -0,0 +1 @@
+ALTER TABLE track ADD COLUMN "lyrics_pinned" BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,14 @@
+ALTER TABLE lyrics ADD COLUMN "provider_identifier" TEXT;
+
+CREATE TABLE lyrics_blocklist (
+  "id" INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+  "created_at" DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+  "provider" TEXT NOT NULL,
+  "identifier" TEXT NOT NULL,
+
+  "track_id" INTEGER NOT NULL,
+
+  FOREIGN KEY ("track_id") REFERENCES track("id") ON DELETE CASCADE,
+  UNIQUE ("track_id", "provider", "identifier")
+);
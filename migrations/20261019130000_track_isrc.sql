@@ -0,0 +1 @@
+ALTER TABLE track ADD COLUMN "isrc" TEXT;
@@ -0,0 +1,19 @@
+CREATE TABLE track_search (
+  "id" INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+  "created_at" DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+  "outcome" TEXT NOT NULL,
+  "min_score" REAL NOT NULL,
+  "providers" TEXT NOT NULL, -- JSON
+  "candidates" TEXT NOT NULL, -- JSON
+  "errors" TEXT NOT NULL, -- JSON
+  "chosen" TEXT, -- JSON
+  "error" TEXT,
+
+  "job_id" INTEGER,
+  "track_id" INTEGER NOT NULL,
+
+  FOREIGN KEY ("track_id") REFERENCES track("id") ON DELETE CASCADE
+);
+
+CREATE INDEX idx_track_search_track_id ON track_search(track_id);
@@ -0,0 +1,7 @@
+ALTER TABLE track ADD COLUMN "last_searched_at" DATETIME;
+
+ALTER TABLE track ADD COLUMN "search_attempts" INTEGER NOT NULL DEFAULT 0;
+
+ALTER TABLE track ADD COLUMN "next_search_at" DATETIME;
+
+CREATE INDEX idx_track_next_search_at ON track(next_search_at);
@@ -0,0 +1 @@
+ALTER TABLE "job" ADD COLUMN "lane" TEXT NOT NULL DEFAULT 'library';
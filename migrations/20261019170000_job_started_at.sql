@@ -0,0 +1,3 @@
+ALTER TABLE "job" ADD COLUMN "started_at" DATETIME;
+
+CREATE INDEX idx_job_status ON job(status);
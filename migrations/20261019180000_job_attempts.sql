@@ -0,0 +1,7 @@
+ALTER TABLE "job" ADD COLUMN "attempt" INTEGER NOT NULL DEFAULT 1;
+
+ALTER TABLE "job" ADD COLUMN "max_attempts" INTEGER NOT NULL DEFAULT 1;
+
+ALTER TABLE "job" ADD COLUMN "retry_of" INTEGER REFERENCES job(id) ON DELETE SET NULL;
+
+ALTER TABLE "job" ADD COLUMN "run_after" DATETIME;
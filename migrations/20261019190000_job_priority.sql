@@ -0,0 +1,3 @@
+ALTER TABLE "job" ADD COLUMN "priority" INTEGER NOT NULL DEFAULT 0;
+
+CREATE INDEX idx_job_pending ON job(status, priority DESC, id);
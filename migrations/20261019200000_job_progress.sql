@@ -0,0 +1 @@
+ALTER TABLE "job" ADD COLUMN "progress" TEXT; -- JSON
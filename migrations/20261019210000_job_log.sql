@@ -0,0 +1,14 @@
+CREATE TABLE job_log (
+  "id" INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+  "created_at" DATETIME DEFAULT CURRENT_TIMESTAMP,
+
+  "job_id" INTEGER NOT NULL,
+  "level" TEXT NOT NULL,
+  "message" TEXT NOT NULL,
+
+  FOREIGN KEY ("job_id") REFERENCES job("id") ON DELETE CASCADE
+);
+
+CREATE INDEX idx_job_log_job_id ON job_log(job_id, id);
+
+CREATE INDEX idx_job_log_created_at ON job_log(created_at);
@@ -0,0 +1,97 @@
+import type { JobLog } from "@/domain/jobLog";
+import { jobLogsQueryOptions } from "@/queries/job";
+import { useQuery } from "@tanstack/react-query";
+import { format } from "date-fns";
+import { LoaderIcon } from "lucide-react";
+import { useState, type PropsWithChildren } from "react";
+import { Button } from "../ui/Button";
+import {
+  Dialog,
+  DialogBody,
+  DialogClose,
+  DialogContent,
+  DialogFooter,
+  DialogHeader,
+  DialogTitle,
+} from "../ui/Dialog";
+
+const PAGE_SIZE = 200;
+
+export function JobLogsDialog({
+  jobId,
+  children,
+}: { jobId: number } & PropsWithChildren) {
+  const [open, setOpen] = useState(false);
+  const [page, setPage] = useState(0);
+  const { data: logs } = useQuery({
+    ...jobLogsQueryOptions(jobId, { page, size: PAGE_SIZE }),
+    enabled: open,
+  });
+  const pageCount = logs ? Math.ceil(logs.total / PAGE_SIZE) : 0;
+
+  return (
+    <Dialog open={open} onOpenChange={setOpen}>
+      {children}
+      <DialogContent>
+        <DialogHeader>
+          <DialogTitle>Job #{jobId} logs</DialogTitle>
+        </DialogHeader>
+        <DialogBody>
+          {logs ? (
+            logs.items.length > 0 ? (
+              <ol className="flex flex-col font-mono text-xs">
+                {logs.items.map((log) => (
+                  <JobLogItem key={log.id} log={log} />
+                ))}
+              </ol>
+            ) : (
+              <div className="text-gray-400 text-sm py-4 text-center">
+                This job has no logs
+              </div>
+            )
+          ) : (
+            <div className="flex flex-row items-center justify-center gap-2 text-gray-400 text-sm py-4">
+              <LoaderIcon className="animate-spin size-5.5" />
+              Loading logs...
+            </div>
+          )}
+        </DialogBody>
+        <DialogFooter>
+          {pageCount > 1 && (
+            <>
+              <Button
+                variant="outline"
+                disabled={page === 0}
+                onClick={() => setPage(page - 1)}
+              >
+                Previous
+              </Button>
+              <Button
+                variant="outline"
+                disabled={page >= pageCount - 1}
+                onClick={() => setPage(page + 1)}
+              >
+                Next
+              </Button>
+            </>
+          )}
+          <DialogClose render={<Button variant="outline">Close</Button>} />
+        </DialogFooter>
+      </DialogContent>
+    </Dialog>
+  );
+}
+
+function JobLogItem({ log }: { log: JobLog }) {
+  return (
+    <li
+      data-level={log.level}
+      className="flex flex-row gap-2 text-gray-300 data-[level=warn]:text-yellow-400 data-[level=error]:text-failure"
+    >
+      <span className="text-gray-500 shrink-0">
+        {format(log.createdAt, "HH:mm:ss")}
+      </span>
+      <span className="whitespace-pre-wrap break-all">{log.message}</span>
+    </li>
+  );
+}
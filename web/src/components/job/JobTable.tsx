@@ -1,8 +1,12 @@
 /* eslint-disable react-hooks/incompatible-library */
 import type { Job } from "@/domain/job";
-import { jobsQueryOptions } from "@/queries/job";
+import {
+  cancelJobMutationOptions,
+  jobsQueryOptions,
+  retryJobMutationOptions,
+} from "@/queries/job";
 import { toPageable } from "@/utils/query";
-import { useQuery } from "@tanstack/react-query";
+import { useMutation, useQuery } from "@tanstack/react-query";
 import {
   getCoreRowModel,
   useReactTable,
@@ -19,6 +23,11 @@ import {
   TableContainer,
 } from "../ui/Table";
 import { sentenceCase } from "change-case";
+import { RotateCcwIcon, ScrollTextIcon, SquareIcon } from "lucide-react";
+import { Button } from "../ui/Button";
+import { Progress, ProgressBar, ProgressLabel } from "../ui/Progress";
+import { DialogTrigger } from "../ui/Dialog";
+import { JobLogsDialog } from "./JobLogsDialog";
 
 const columns: ColumnDef<Job>[] = [
   {
@@ -53,6 +62,98 @@ const columns: ColumnDef<Job>[] = [
     },
     accessorFn: (job) => sentenceCase(job.status),
   },
+  {
+    id: "progress",
+    header: "Progress",
+    meta: {
+      className: "w-48",
+    },
+    cell: ({ row }) => {
+      const { progress, status } = row.original;
+      if (progress == null || status !== "running") return null;
+      const ratio = progress.total > 0 ? progress.current / progress.total : 0;
+      return (
+        <Progress
+          title={[progress.phase, progress.entity].filter(Boolean).join(": ")}
+        >
+          <ProgressBar style={{ width: `${ratio * 100}%` }} />
+          <ProgressLabel>
+            {progress.current}/{progress.total}
+          </ProgressLabel>
+        </Progress>
+      );
+    },
+  },
+  {
+    id: "priority",
+    header: "Priority",
+    meta: {
+      className: "w-24 text-center",
+    },
+    accessorKey: "priority",
+  },
+  {
+    id: "attempt",
+    header: "Attempt",
+    meta: {
+      className: "w-24 text-center",
+    },
+    accessorFn: (job) => `${job.attempt}/${job.maxAttempts}`,
+  },
+  {
+    id: "actions",
+    header: "Actions",
+    meta: {
+      className: "w-28 text-center",
+    },
+    cell: ({ row }) => {
+      // eslint-disable-next-line react-hooks/rules-of-hooks
+      const cancelJob = useMutation(cancelJobMutationOptions());
+      // eslint-disable-next-line react-hooks/rules-of-hooks
+      const retryJob = useMutation(retryJobMutationOptions());
+      const job = row.original;
+
+      return (
+        <div className="flex flex-row items-center justify-center gap-1">
+          <JobLogsDialog jobId={job.id}>
+            <DialogTrigger
+              render={
+                <Button variant="ghost" size="icon-sm" title="Logs">
+                  <ScrollTextIcon />
+                </Button>
+              }
+            />
+          </JobLogsDialog>
+          {job.status === "failed" && (
+            <Button
+              variant="ghost"
+              size="icon-sm"
+              title="Retry"
+              disabled={retryJob.isPending}
+              onClick={() => {
+                retryJob.mutate(job.id);
+              }}
+            >
+              <RotateCcwIcon />
+            </Button>
+          )}
+          {(job.status === "pending" || job.status === "running") && (
+            <Button
+              variant="ghost"
+              size="icon-sm"
+              title="Cancel"
+              disabled={cancelJob.isPending}
+              onClick={() => {
+                cancelJob.mutate(job.id);
+              }}
+            >
+              <SquareIcon className="fill-current" />
+            </Button>
+          )}
+        </div>
+      );
+    },
+  },
 ];
 
 export function JobTable() {
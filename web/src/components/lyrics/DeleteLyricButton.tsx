@@ -5,9 +5,11 @@ import { deleteLyricMutationOptions } from "@/queries/lyrics";
 
 export function DeleteLyricsButton({
   lyricId,
+  blocklist,
   ...rest
 }: ComponentProps<typeof Button> & {
   lyricId: number;
+  blocklist?: boolean;
 }) {
   const deleteLyric = useMutation(deleteLyricMutationOptions());
 
@@ -17,7 +19,7 @@ export function DeleteLyricsButton({
       className="text-failure"
       disabled={deleteLyric.isPending}
       onClick={() => {
-        deleteLyric.mutate(lyricId);
+        deleteLyric.mutate({ id: lyricId, blocklist });
       }}
       {...rest}
     />
@@ -14,6 +14,7 @@ import {
 import { useState, type ReactElement } from "react";
 import { Button } from "../ui/Button";
 import { DeleteLyricsButton } from "./DeleteLyricButton";
+import { PublishLyricsButton } from "./PublishLyricButton";
 
 export function LyricsDialog({
   lyricId,
@@ -38,12 +39,37 @@ export function LyricsDialog({
       <DialogContent>
         <DialogHeader>
           <DialogTitle>Lyrics</DialogTitle>
-          <DialogDescription>{lyric?.filePath}</DialogDescription>
+          <DialogDescription>
+            {lyric?.filePath}
+            {lyric?.publishedAt && (
+              <span className="block">Published to LrcLib on {lyric.publishedAt}</span>
+            )}
+            {lyric?.publishError && (
+              <span className="block text-failure">
+                Publishing failed: {lyric.publishError}
+              </span>
+            )}
+          </DialogDescription>
         </DialogHeader>
         <DialogBody className="whitespace-pre-wrap font-mono text-sm">
           {content?.text}
         </DialogBody>
         <DialogFooter>
+          <PublishLyricsButton
+            lyricId={lyricId}
+            title="Contribute these lyrics to LrcLib"
+          >
+            Publish to LrcLib
+          </PublishLyricsButton>
+          {lyric?.providerIdentifier && (
+            <DeleteLyricsButton
+              lyricId={lyricId}
+              blocklist
+              title="Delete and never import this result again for this track"
+            >
+              Delete & blocklist
+            </DeleteLyricsButton>
+          )}
           <DeleteLyricsButton lyricId={lyricId}>Delete</DeleteLyricsButton>
           <DialogClose render={<Button variant="outline">Close</Button>} />
         </DialogFooter>
@@ -0,0 +1,28 @@
+import type { ComponentProps } from "react";
+import { Button } from "../ui/Button";
+import { useMutation } from "@tanstack/react-query";
+import { publishLyricMutationOptions } from "@/queries/lyrics";
+import { toastPromise } from "@/hooks/notification/useNotificationState";
+
+export function PublishLyricsButton({
+  lyricId,
+  ...rest
+}: ComponentProps<typeof Button> & {
+  lyricId: number;
+}) {
+  const publishLyric = useMutation(publishLyricMutationOptions());
+
+  return (
+    <Button
+      variant="outline"
+      disabled={publishLyric.isPending}
+      onClick={() => {
+        toastPromise(publishLyric.mutateAsync(lyricId), {
+          title: "Publishing",
+          success: "Published",
+        });
+      }}
+      {...rest}
+    />
+  );
+}
@@ -42,13 +42,15 @@ function AlbumNotification({ prefix }: { prefix: string }) {
 
 function TrackNotification({ prefix }: { prefix: string }) {
   const { data: track } = useQuery(
-    trackQueryOptions(useJob<"scanTrack" | "searchTrack">().payload.trackId)
+    trackQueryOptions(useJob<"importResult" | "scanTrack" | "searchTrack">().payload.trackId)
   );
   return `${prefix} ${track?.title}`;
 }
 
 const elements: Record<JobPayload["type"], React.ReactNode> = {
   importLyrics: <ImportLyricsNotification />,
+  importResult: <TrackNotification prefix="Importing" />,
+  publishLyrics: "Publishing lyrics",
   scanLibrary: "Scanning library",
   scanArtist: <ArtistNotification prefix="Scanning" />,
   scanAlbum: <AlbumNotification prefix="Scanning" />,
@@ -57,6 +59,7 @@ const elements: Record<JobPayload["type"], React.ReactNode> = {
   searchArtist: <ArtistNotification prefix="Searching" />,
   searchAlbum: <AlbumNotification prefix="Searching" />,
   searchTrack: <TrackNotification prefix="Searching" />,
+  searchWanted: "Searching wanted tracks",
   syncLibrary: "Syncing library",
   syncArtist: <ArtistNotification prefix="Syncing" />,
 };
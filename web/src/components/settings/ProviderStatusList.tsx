@@ -0,0 +1,37 @@
+import { providerStatusesQueryOptions } from "@/queries/provider";
+import { useQuery } from "@tanstack/react-query";
+import { Tag } from "../ui/Tag";
+
+const stateLabels = {
+  closed: "Healthy",
+  open: "Paused",
+  halfOpen: "Recovering",
+};
+
+export function ProviderStatusList() {
+  const { data: statuses } = useQuery(providerStatusesQueryOptions());
+
+  return (
+    <ul className="flex flex-col gap-1">
+      {statuses?.map((status) => (
+        <li
+          key={status.name}
+          className="flex flex-row items-center gap-2 text-sm"
+        >
+          <Tag>{status.name}</Tag>
+          <Tag variant="secondary">{stateLabels[status.state]}</Tag>
+          {status.retryAfterSecs != null && (
+            <span className="text-xs text-gray-400">
+              retry in {status.retryAfterSecs}s
+            </span>
+          )}
+          {status.state !== "closed" && status.lastError && (
+            <span className="text-xs text-gray-400 truncate">
+              {status.lastError}
+            </span>
+          )}
+        </li>
+      ))}
+    </ul>
+  );
+}
@@ -34,6 +34,7 @@ import {
   SliderTrack,
   SliderValue,
 } from "../ui/Slider";
+import { ProviderStatusList } from "./ProviderStatusList";
 
 const settingsFormSchema = settingsSchema;
 
@@ -61,8 +62,15 @@ export function SettingsForm({
     >
       <SettingsFormGeneralGroup />
       <SettingsFormLyricsGroup />
+      <SettingsFormScoringGroup />
       <SettingsFormAuthGroup />
       <SettingsFormLidarrGroup />
+      <SettingsFormProvidersGroup />
+      <SettingsFormProviderLimitsGroup />
+      <SettingsFormCacheGroup />
+      <SettingsFormWantedGroup />
+      <SettingsFormWorkersGroup />
+      <SettingsFormJobLogsGroup />
     </Form>
   );
 }
@@ -132,6 +140,146 @@ function SettingsFormLyricsGroup() {
           </FormField>
         )}
       />
+      <FormController
+        control={form.control}
+        name="lyrics.providerTimeout"
+        render={({ field }) => (
+          <FormField>
+            <FormLabel>Provider timeout (s)</FormLabel>
+            <NumberField {...field} className="w-48">
+              <NumberFieldDecrement />
+              <NumberFieldInput />
+              <NumberFieldIncrement />
+            </NumberField>
+            <FormError />
+          </FormField>
+        )}
+      />
+    </FormGroup>
+  );
+}
+
+function SettingsFormScoringGroup() {
+  const form = useFormContext<SettingsFormValues>();
+
+  return (
+    <FormGroup>
+      <FormGroupTitle>Scoring</FormGroupTitle>
+      <FormController
+        control={form.control}
+        name="lyrics.weights.trackName"
+        render={({ field }) => (
+          <FormField>
+            <FormLabel>Title weight</FormLabel>
+            <NumberField {...field} step={0.05} min={0} className="w-48">
+              <NumberFieldDecrement />
+              <NumberFieldInput />
+              <NumberFieldIncrement />
+            </NumberField>
+            <FormError />
+          </FormField>
+        )}
+      />
+      <FormController
+        control={form.control}
+        name="lyrics.weights.artistName"
+        render={({ field }) => (
+          <FormField>
+            <FormLabel>Artist weight</FormLabel>
+            <NumberField {...field} step={0.05} min={0} className="w-48">
+              <NumberFieldDecrement />
+              <NumberFieldInput />
+              <NumberFieldIncrement />
+            </NumberField>
+            <FormError />
+          </FormField>
+        )}
+      />
+      <FormController
+        control={form.control}
+        name="lyrics.weights.albumTitle"
+        render={({ field }) => (
+          <FormField>
+            <FormLabel>Album weight</FormLabel>
+            <NumberField {...field} step={0.05} min={0} className="w-48">
+              <NumberFieldDecrement />
+              <NumberFieldInput />
+              <NumberFieldIncrement />
+            </NumberField>
+            <FormError />
+          </FormField>
+        )}
+      />
+      <FormController
+        control={form.control}
+        name="lyrics.weights.duration"
+        render={({ field }) => (
+          <FormField>
+            <FormLabel>Duration weight</FormLabel>
+            <NumberField {...field} step={0.05} min={0} className="w-48">
+              <NumberFieldDecrement />
+              <NumberFieldInput />
+              <NumberFieldIncrement />
+            </NumberField>
+            <FormError />
+          </FormField>
+        )}
+      />
+      <FormController
+        control={form.control}
+        name="lyrics.normalization.lowercase"
+        render={({ field }) => (
+          <FormField>
+            <FormLabel>Ignore case</FormLabel>
+            <Switch {...field} />
+            <FormError />
+          </FormField>
+        )}
+      />
+      <FormController
+        control={form.control}
+        name="lyrics.normalization.stripPunctuation"
+        render={({ field }) => (
+          <FormField>
+            <FormLabel>Ignore punctuation</FormLabel>
+            <Switch {...field} />
+            <FormError />
+          </FormField>
+        )}
+      />
+      <FormController
+        control={form.control}
+        name="lyrics.normalization.stripFeatures"
+        render={({ field }) => (
+          <FormField>
+            <FormLabel>Ignore featured artists</FormLabel>
+            <Switch {...field} />
+            <FormError />
+          </FormField>
+        )}
+      />
+      <FormController
+        control={form.control}
+        name="lyrics.normalization.stripSuffixes"
+        render={({ field }) => (
+          <FormField>
+            <FormLabel>Ignore remaster/live/edition suffixes</FormLabel>
+            <Switch {...field} />
+            <FormError />
+          </FormField>
+        )}
+      />
+      <FormController
+        control={form.control}
+        name="lyrics.normalization.stripDiacritics"
+        render={({ field }) => (
+          <FormField>
+            <FormLabel>Ignore diacritics</FormLabel>
+            <Switch {...field} />
+            <FormError />
+          </FormField>
+        )}
+      />
     </FormGroup>
   );
 }
@@ -230,3 +378,615 @@ function SettingsFormLidarrGroup() {
     </FormGroup>
   );
 }
+
+function SettingsFormProvidersGroup() {
+  const form = useFormContext<SettingsFormValues>();
+  const geniusEnabled = useWatch({
+    control: form.control,
+    name: "providers.genius.enabled",
+  });
+  const localEnabled = useWatch({
+    control: form.control,
+    name: "providers.local.enabled",
+  });
+  const templateEnabled = useWatch({
+    control: form.control,
+    name: "providers.template.enabled",
+  });
+  const scriptEnabled = useWatch({
+    control: form.control,
+    name: "providers.script.enabled",
+  });
+  const musixmatchEnabled = useWatch({
+    control: form.control,
+    name: "providers.musixmatch.enabled",
+  });
+
+  return (
+    <FormGroup>
+      <FormGroupTitle>Providers</FormGroupTitle>
+      <FormController
+        control={form.control}
+        name="providers.genius.enabled"
+        render={({ field }) => (
+          <FormField>
+            <FormLabel>Genius</FormLabel>
+            <Switch {...field} />
+            <FormError />
+          </FormField>
+        )}
+      />
+      {geniusEnabled && (
+        <FormController
+          control={form.control}
+          name="providers.genius.accessToken"
+          render={({ field }) => (
+            <FormField>
+              <FormLabel>Genius Access Token</FormLabel>
+              <Input {...field} value={field.value ?? ""} />
+              <FormError />
+            </FormField>
+          )}
+        />
+      )}
+      <FormController
+        control={form.control}
+        name="providers.local.enabled"
+        render={({ field }) => (
+          <FormField>
+            <FormLabel>Local folder</FormLabel>
+            <Switch {...field} />
+            <FormError />
+          </FormField>
+        )}
+      />
+      {localEnabled && (
+        <FormController
+          control={form.control}
+          name="providers.local.path"
+          render={({ field }) => (
+            <FormField>
+              <FormLabel>Local folder path</FormLabel>
+              <Input {...field} />
+              <FormError />
+            </FormField>
+          )}
+        />
+      )}
+      <FormController
+        control={form.control}
+        name="providers.musixmatch.enabled"
+        render={({ field }) => (
+          <FormField>
+            <FormLabel>Musixmatch</FormLabel>
+            <Switch {...field} />
+            <FormError />
+          </FormField>
+        )}
+      />
+      {musixmatchEnabled && (
+        <>
+          <FormController
+            control={form.control}
+            name="providers.musixmatch.token"
+            render={({ field }) => (
+              <FormField>
+                <FormLabel>Musixmatch Token</FormLabel>
+                <Input {...field} value={field.value ?? ""} />
+                <FormError />
+              </FormField>
+            )}
+          />
+          <FormController
+            control={form.control}
+            name="providers.musixmatch.enhanced"
+            render={({ field }) => (
+              <FormField>
+                <FormLabel>Word-level timing (enhanced LRC)</FormLabel>
+                <Switch {...field} />
+                <FormError />
+              </FormField>
+            )}
+          />
+        </>
+      )}
+      <FormController
+        control={form.control}
+        name="providers.netease.enabled"
+        render={({ field }) => (
+          <FormField>
+            <FormLabel>NetEase</FormLabel>
+            <Switch {...field} />
+            <FormError />
+          </FormField>
+        )}
+      />
+      <FormController
+        control={form.control}
+        name="providers.script.enabled"
+        render={({ field }) => (
+          <FormField>
+            <FormLabel>External script</FormLabel>
+            <Switch {...field} />
+            <FormError />
+          </FormField>
+        )}
+      />
+      {scriptEnabled && (
+        <>
+          <FormController
+            control={form.control}
+            name="providers.script.path"
+            render={({ field }) => (
+              <FormField>
+                <FormLabel>Script path</FormLabel>
+                <Input {...field} />
+                <FormError />
+              </FormField>
+            )}
+          />
+          <FormController
+            control={form.control}
+            name="providers.script.timeout"
+            render={({ field }) => (
+              <FormField>
+                <FormLabel>Script timeout (s)</FormLabel>
+                <NumberField {...field} className="w-48">
+                  <NumberFieldDecrement />
+                  <NumberFieldInput />
+                  <NumberFieldIncrement />
+                </NumberField>
+                <FormError />
+              </FormField>
+            )}
+          />
+        </>
+      )}
+      <FormController
+        control={form.control}
+        name="providers.template.enabled"
+        render={({ field }) => (
+          <FormField>
+            <FormLabel>HTTP template</FormLabel>
+            <Switch {...field} />
+            <FormError />
+          </FormField>
+        )}
+      />
+      {templateEnabled && (
+        <>
+          <FormController
+            control={form.control}
+            name="providers.template.searchUrl"
+            render={({ field }) => (
+              <FormField>
+                <FormLabel>Search URL template</FormLabel>
+                <Input {...field} />
+                <FormError />
+              </FormField>
+            )}
+          />
+          <FormController
+            control={form.control}
+            name="providers.template.resultsPointer"
+            render={({ field }) => (
+              <FormField>
+                <FormLabel>Results pointer</FormLabel>
+                <Input {...field} />
+                <FormError />
+              </FormField>
+            )}
+          />
+          <FormController
+            control={form.control}
+            name="providers.template.fields.identifier"
+            render={({ field }) => (
+              <FormField>
+                <FormLabel>Identifier pointer</FormLabel>
+                <Input {...field} />
+                <FormError />
+              </FormField>
+            )}
+          />
+          <FormController
+            control={form.control}
+            name="providers.template.fields.name"
+            render={({ field }) => (
+              <FormField>
+                <FormLabel>Name pointer</FormLabel>
+                <Input {...field} />
+                <FormError />
+              </FormField>
+            )}
+          />
+          <FormController
+            control={form.control}
+            name="providers.template.fields.trackName"
+            render={({ field }) => (
+              <FormField>
+                <FormLabel>Track name pointer</FormLabel>
+                <Input {...field} />
+                <FormError />
+              </FormField>
+            )}
+          />
+          <FormController
+            control={form.control}
+            name="providers.template.fields.artistName"
+            render={({ field }) => (
+              <FormField>
+                <FormLabel>Artist name pointer</FormLabel>
+                <Input {...field} />
+                <FormError />
+              </FormField>
+            )}
+          />
+          <FormController
+            control={form.control}
+            name="providers.template.fields.albumTitle"
+            render={({ field }) => (
+              <FormField>
+                <FormLabel>Album title pointer</FormLabel>
+                <Input {...field} />
+                <FormError />
+              </FormField>
+            )}
+          />
+          <FormController
+            control={form.control}
+            name="providers.template.fields.duration"
+            render={({ field }) => (
+              <FormField>
+                <FormLabel>Duration pointer</FormLabel>
+                <Input {...field} />
+                <FormError />
+              </FormField>
+            )}
+          />
+          <FormController
+            control={form.control}
+            name="providers.template.fields.isrc"
+            render={({ field }) => (
+              <FormField>
+                <FormLabel>ISRC pointer</FormLabel>
+                <Input {...field} />
+                <FormError />
+              </FormField>
+            )}
+          />
+          <FormController
+            control={form.control}
+            name="providers.template.fields.musicbrainzId"
+            render={({ field }) => (
+              <FormField>
+                <FormLabel>MusicBrainz recording ID pointer</FormLabel>
+                <Input {...field} />
+                <FormError />
+              </FormField>
+            )}
+          />
+          <FormController
+            control={form.control}
+            name="providers.template.fields.syncedLyrics"
+            render={({ field }) => (
+              <FormField>
+                <FormLabel>Synced lyrics pointer</FormLabel>
+                <Input {...field} />
+                <FormError />
+              </FormField>
+            )}
+          />
+          <FormController
+            control={form.control}
+            name="providers.template.fields.plainLyrics"
+            render={({ field }) => (
+              <FormField>
+                <FormLabel>Plain lyrics pointer</FormLabel>
+                <Input {...field} />
+                <FormError />
+              </FormField>
+            )}
+          />
+          <FormController
+            control={form.control}
+            name="providers.template.downloadUrl"
+            render={({ field }) => (
+              <FormField>
+                <FormLabel>Download URL template</FormLabel>
+                <Input {...field} />
+                <FormError />
+              </FormField>
+            )}
+          />
+        </>
+      )}
+    </FormGroup>
+  );
+}
+
+function SettingsFormProviderLimitsGroup() {
+  const form = useFormContext<SettingsFormValues>();
+
+  return (
+    <FormGroup>
+      <FormGroupTitle>Provider limits</FormGroupTitle>
+      <ProviderStatusList />
+      <FormController
+        control={form.control}
+        name="providers.limits.requestsPerSecond"
+        render={({ field }) => (
+          <FormField>
+            <FormLabel>Requests per second</FormLabel>
+            <NumberField {...field} className="w-48">
+              <NumberFieldDecrement />
+              <NumberFieldInput />
+              <NumberFieldIncrement />
+            </NumberField>
+            <FormError />
+          </FormField>
+        )}
+      />
+      <FormController
+        control={form.control}
+        name="providers.limits.maxInFlight"
+        render={({ field }) => (
+          <FormField>
+            <FormLabel>Max in-flight requests</FormLabel>
+            <NumberField {...field} className="w-48">
+              <NumberFieldDecrement />
+              <NumberFieldInput />
+              <NumberFieldIncrement />
+            </NumberField>
+            <FormError />
+          </FormField>
+        )}
+      />
+      <FormController
+        control={form.control}
+        name="providers.limits.maxRetries"
+        render={({ field }) => (
+          <FormField>
+            <FormLabel>Max retries</FormLabel>
+            <NumberField {...field} className="w-48">
+              <NumberFieldDecrement />
+              <NumberFieldInput />
+              <NumberFieldIncrement />
+            </NumberField>
+            <FormError />
+          </FormField>
+        )}
+      />
+      <FormController
+        control={form.control}
+        name="providers.limits.retryBackoffMs"
+        render={({ field }) => (
+          <FormField>
+            <FormLabel>Retry backoff (ms)</FormLabel>
+            <NumberField {...field} className="w-48">
+              <NumberFieldDecrement />
+              <NumberFieldInput />
+              <NumberFieldIncrement />
+            </NumberField>
+            <FormError />
+          </FormField>
+        )}
+      />
+      <FormController
+        control={form.control}
+        name="providers.limits.failureThreshold"
+        render={({ field }) => (
+          <FormField>
+            <FormLabel>Failures before pausing</FormLabel>
+            <NumberField {...field} className="w-48">
+              <NumberFieldDecrement />
+              <NumberFieldInput />
+              <NumberFieldIncrement />
+            </NumberField>
+            <FormError />
+          </FormField>
+        )}
+      />
+      <FormController
+        control={form.control}
+        name="providers.limits.cooldown"
+        render={({ field }) => (
+          <FormField>
+            <FormLabel>Pause duration (s)</FormLabel>
+            <NumberField {...field} className="w-48">
+              <NumberFieldDecrement />
+              <NumberFieldInput />
+              <NumberFieldIncrement />
+            </NumberField>
+            <FormError />
+          </FormField>
+        )}
+      />
+    </FormGroup>
+  );
+}
+
+function SettingsFormCacheGroup() {
+  const form = useFormContext<SettingsFormValues>();
+
+  return (
+    <FormGroup>
+      <FormGroupTitle>Cache</FormGroupTitle>
+      <FormController
+        control={form.control}
+        name="cache.enabled"
+        render={({ field }) => (
+          <FormField>
+            <FormLabel>Cache provider responses</FormLabel>
+            <Switch {...field} />
+            <FormError />
+          </FormField>
+        )}
+      />
+      <FormController
+        control={form.control}
+        name="cache.hitTtl"
+        render={({ field }) => (
+          <FormField>
+            <FormLabel>Hit TTL (s)</FormLabel>
+            <NumberField {...field} className="w-48">
+              <NumberFieldDecrement />
+              <NumberFieldInput />
+              <NumberFieldIncrement />
+            </NumberField>
+            <FormError />
+          </FormField>
+        )}
+      />
+      <FormController
+        control={form.control}
+        name="cache.missTtl"
+        render={({ field }) => (
+          <FormField>
+            <FormLabel>Miss TTL (s)</FormLabel>
+            <NumberField {...field} className="w-48">
+              <NumberFieldDecrement />
+              <NumberFieldInput />
+              <NumberFieldIncrement />
+            </NumberField>
+            <FormError />
+          </FormField>
+        )}
+      />
+    </FormGroup>
+  );
+}
+
+function SettingsFormWantedGroup() {
+  const form = useFormContext<SettingsFormValues>();
+
+  return (
+    <FormGroup>
+      <FormGroupTitle>Wanted</FormGroupTitle>
+      <FormController
+        control={form.control}
+        name="wanted.budget"
+        render={({ field }) => (
+          <FormField>
+            <FormLabel>Tracks searched per run</FormLabel>
+            <NumberField {...field} className="w-48">
+              <NumberFieldDecrement />
+              <NumberFieldInput />
+              <NumberFieldIncrement />
+            </NumberField>
+            <FormError />
+          </FormField>
+        )}
+      />
+      <FormController
+        control={form.control}
+        name="wanted.concurrency"
+        render={({ field }) => (
+          <FormField>
+            <FormLabel>Concurrent searches</FormLabel>
+            <NumberField {...field} className="w-48">
+              <NumberFieldDecrement />
+              <NumberFieldInput />
+              <NumberFieldIncrement />
+            </NumberField>
+            <FormError />
+          </FormField>
+        )}
+      />
+    </FormGroup>
+  );
+}
+
+function SettingsFormWorkersGroup() {
+  const form = useFormContext<SettingsFormValues>();
+
+  return (
+    <FormGroup>
+      <FormGroupTitle>Workers</FormGroupTitle>
+      <FormController
+        control={form.control}
+        name="workers.library"
+        render={({ field }) => (
+          <FormField>
+            <FormLabel>Library jobs</FormLabel>
+            <NumberField {...field} className="w-48">
+              <NumberFieldDecrement />
+              <NumberFieldInput />
+              <NumberFieldIncrement />
+            </NumberField>
+            <FormError />
+          </FormField>
+        )}
+      />
+      <FormController
+        control={form.control}
+        name="workers.search"
+        render={({ field }) => (
+          <FormField>
+            <FormLabel>Search jobs</FormLabel>
+            <NumberField {...field} className="w-48">
+              <NumberFieldDecrement />
+              <NumberFieldInput />
+              <NumberFieldIncrement />
+            </NumberField>
+            <FormError />
+          </FormField>
+        )}
+      />
+      <FormController
+        control={form.control}
+        name="workers.user"
+        render={({ field }) => (
+          <FormField>
+            <FormLabel>Manual jobs</FormLabel>
+            <NumberField {...field} className="w-48">
+              <NumberFieldDecrement />
+              <NumberFieldInput />
+              <NumberFieldIncrement />
+            </NumberField>
+            <FormError />
+          </FormField>
+        )}
+      />
+    </FormGroup>
+  );
+}
+
+function SettingsFormJobLogsGroup() {
+  const form = useFormContext<SettingsFormValues>();
+
+  return (
+    <FormGroup>
+      <FormGroupTitle>Job logs</FormGroupTitle>
+      <FormController
+        control={form.control}
+        name="jobLogs.retentionDays"
+        render={({ field }) => (
+          <FormField>
+            <FormLabel>Retention (days)</FormLabel>
+            <NumberField {...field} className="w-48">
+              <NumberFieldDecrement />
+              <NumberFieldInput />
+              <NumberFieldIncrement />
+            </NumberField>
+            <FormError />
+          </FormField>
+        )}
+      />
+      <FormController
+        control={form.control}
+        name="jobLogs.maxPerJob"
+        render={({ field }) => (
+          <FormField>
+            <FormLabel>Lines kept per job</FormLabel>
+            <NumberField {...field} className="w-48">
+              <NumberFieldDecrement />
+              <NumberFieldInput />
+              <NumberFieldIncrement />
+            </NumberField>
+            <FormError />
+          </FormField>
+        )}
+      />
+    </FormGroup>
+  );
+}
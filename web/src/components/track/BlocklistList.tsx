@@ -0,0 +1,60 @@
+import {
+  clearTrackBlocklistMutationOptions,
+  trackBlocklistQueryOptions,
+} from "@/queries/track";
+import { useMutation, useQuery } from "@tanstack/react-query";
+import { BanIcon, XIcon } from "lucide-react";
+import { Button } from "../ui/Button";
+import { Tag } from "../ui/Tag";
+
+export function BlocklistList({
+  trackId,
+  enabled = true,
+}: {
+  trackId: number;
+  enabled?: boolean;
+}) {
+  const { data: entries } = useQuery({
+    ...trackBlocklistQueryOptions(trackId),
+    enabled,
+  });
+  const clear = useMutation(clearTrackBlocklistMutationOptions(trackId));
+
+  if (!entries || entries.length === 0) return null;
+  return (
+    <div className="flex flex-col gap-1 mt-4">
+      <div className="flex flex-row items-center justify-between">
+        <h3 className="text-sm font-medium">Blocklisted results</h3>
+        <Button
+          size="sm"
+          variant="outline"
+          disabled={clear.isPending}
+          onClick={() => clear.mutate(undefined)}
+        >
+          Clear
+        </Button>
+      </div>
+      <ul className="flex flex-col gap-1">
+        {entries.map((entry) => (
+          <li
+            key={entry.id}
+            className="flex flex-row items-center gap-2 text-xs text-gray-400"
+          >
+            <BanIcon className="text-failure size-4 shrink-0" />
+            <Tag>{entry.provider}</Tag>
+            <span className="truncate flex-1">{entry.identifier}</span>
+            <Button
+              size="icon-sm"
+              variant="ghost"
+              title="Remove from blocklist"
+              disabled={clear.isPending}
+              onClick={() => clear.mutate(entry.id)}
+            >
+              <XIcon />
+            </Button>
+          </li>
+        ))}
+      </ul>
+    </div>
+  );
+}
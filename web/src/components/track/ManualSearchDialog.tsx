@@ -12,9 +12,10 @@ import {
   DialogHeader,
   DialogTitle,
 } from "../ui/Dialog";
-import { ProviderResultList } from "./ProviderResultList";
+import { ProviderErrorList, ProviderResultList } from "./ProviderResultList";
 import { LoaderIcon } from "lucide-react";
 import { trackQueryOptions } from "@/queries/track";
+import { BlocklistList } from "./BlocklistList";
 
 export function ManualSearchDialog({
   trackId,
@@ -44,7 +45,14 @@ export function ManualSearchDialog({
         </DialogHeader>
         <DialogBody>
           {results ? (
-            <ProviderResultList results={results} />
+            <>
+              <ProviderErrorList errors={results.errors} />
+              <ProviderResultList
+                trackId={trackId}
+                results={results.results}
+              />
+              <BlocklistList trackId={trackId} enabled={open} />
+            </>
           ) : (
             <div className="flex flex-row items-center justify-center gap-2 text-gray-400 text-sm py-4">
               <LoaderIcon className="animate-spin size-5.5" />
@@ -1,10 +1,12 @@
-import type { ProviderResult } from "@/domain/provider";
+import type { ProviderError, ProviderResult } from "@/domain/provider";
 import { Button } from "../ui/Button";
 import {
   CheckIcon,
+  CircleAlertIcon,
   DownloadIcon,
   EyeIcon,
   EyeOffIcon,
+  PinIcon,
   XIcon,
 } from "lucide-react";
 import { formatPercent } from "@/utils/format";
@@ -12,15 +14,25 @@ import { formatDuration, intervalToDuration } from "date-fns";
 import { Tag } from "../ui/Tag";
 import { Accordion } from "@base-ui/react/accordion";
 import { AnimatePresence, motion } from "motion/react";
+import { useMutation } from "@tanstack/react-query";
+import { createJobMutationOptions } from "@/queries/job";
+import { toastPromise } from "@/hooks/notification/useNotificationState";
 
-export function ProviderResultList({ results }: { results: ProviderResult[] }) {
+export function ProviderResultList({
+  trackId,
+  results,
+}: {
+  trackId: number;
+  results: ProviderResult[];
+}) {
   return (
     <Accordion.Root
       render={
         <ol className="flex flex-col gap-2">
           {results.map((result) => (
             <ProviderResultListItem
-              key={`${result.file.identifier}`}
+              key={`${result.provider.name}/${result.file.identifier}`}
+              trackId={trackId}
               result={result}
             />
           ))}
@@ -30,7 +42,44 @@ export function ProviderResultList({ results }: { results: ProviderResult[] }) {
   );
 }
 
-export function ProviderResultListItem({ result }: { result: ProviderResult }) {
+export function ProviderErrorList({ errors }: { errors: ProviderError[] }) {
+  if (errors.length === 0) return null;
+  return (
+    <ul className="flex flex-col gap-1 mb-2">
+      {errors.map((error) => (
+        <li
+          key={error.provider.name}
+          className="flex flex-row items-center gap-2 text-xs text-gray-400"
+        >
+          <CircleAlertIcon className="text-failure size-4 shrink-0" />
+          <Tag>{error.provider.name}</Tag>
+          {error.error}
+        </li>
+      ))}
+    </ul>
+  );
+}
+
+export function ProviderResultListItem({
+  trackId,
+  result,
+}: {
+  trackId: number;
+  result: ProviderResult;
+}) {
+  const createJob = useMutation(createJobMutationOptions());
+  const importResult = (pin: boolean) =>
+    toastPromise(
+      createJob.mutateAsync({
+        type: "importResult",
+        trackId,
+        provider: result.provider.name,
+        identifier: result.file.identifier,
+        pin,
+      }),
+      { title: "Importing", success: "Imported" }
+    );
+
   return (
     <Accordion.Item
       render={
@@ -56,7 +105,18 @@ export function ProviderResultListItem({ result }: { result: ProviderResult }) {
                     </>
                   )}
                 </Tag>
-                <div className="text-xs text-gray-400">
+                <div
+                  className="text-xs text-gray-400"
+                  title={[
+                    `Title: ${formatPercent(result.breakdown.trackName)}`,
+                    `Artist: ${formatPercent(result.breakdown.artistName)}`,
+                    `Album: ${formatPercent(result.breakdown.albumTitle)}`,
+                    `Duration: ${formatPercent(result.breakdown.duration)}`,
+                    ...(result.breakdown.identifier
+                      ? ["Identifier match (ISRC / MusicBrainz)"]
+                      : []),
+                  ].join("\n")}
+                >
                   {formatPercent(result.score)} match -{" "}
                   {formatDuration(
                     intervalToDuration({
@@ -76,9 +136,23 @@ export function ProviderResultListItem({ result }: { result: ProviderResult }) {
                   </Button>
                 }
               />
-              <Button size="icon-sm" variant="outline">
+              <Button
+                size="icon-sm"
+                variant="outline"
+                disabled={createJob.isPending}
+                onClick={() => importResult(false)}
+              >
                 <DownloadIcon />
               </Button>
+              <Button
+                size="icon-sm"
+                variant="outline"
+                title="Import and pin"
+                disabled={createJob.isPending}
+                onClick={() => importResult(true)}
+              >
+                <PinIcon />
+              </Button>
             </div>
           </li>
           <AnimatePresence>
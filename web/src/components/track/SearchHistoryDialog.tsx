@@ -0,0 +1,132 @@
+import type { SearchAttempt, SearchOutcome } from "@/domain/trackSearch";
+import { trackSearchesQueryOptions } from "@/queries/track";
+import { formatPercent } from "@/utils/format";
+import { useQuery } from "@tanstack/react-query";
+import { formatRelative } from "date-fns";
+import { BanIcon, CircleAlertIcon, LoaderIcon } from "lucide-react";
+import { useState, type PropsWithChildren } from "react";
+import { Button } from "../ui/Button";
+import {
+  Dialog,
+  DialogBody,
+  DialogClose,
+  DialogContent,
+  DialogFooter,
+  DialogHeader,
+  DialogTitle,
+} from "../ui/Dialog";
+import { Tag } from "../ui/Tag";
+
+const OUTCOME_LABELS: Record<SearchOutcome, string> = {
+  imported: "Imported",
+  importFailed: "Import failed",
+  noResults: "No results",
+  providerError: "Provider error",
+  belowMinScore: "Below minimum score",
+  blocklisted: "Blocklisted",
+};
+
+export function SearchHistoryDialog({
+  trackId,
+  children,
+}: { trackId: number } & PropsWithChildren) {
+  const [open, setOpen] = useState(false);
+  const { data: searches } = useQuery({
+    ...trackSearchesQueryOptions(trackId, { page: 0, size: 20 }),
+    enabled: open,
+  });
+
+  return (
+    <Dialog open={open} onOpenChange={setOpen}>
+      {children}
+      <DialogContent>
+        <DialogHeader>
+          <DialogTitle>Search history</DialogTitle>
+        </DialogHeader>
+        <DialogBody>
+          {searches ? (
+            searches.items.length > 0 ? (
+              <ul className="flex flex-col gap-4">
+                {searches.items.map((search) => (
+                  <SearchHistoryItem key={search.id} search={search} />
+                ))}
+              </ul>
+            ) : (
+              <div className="text-gray-400 text-sm py-4 text-center">
+                This track has not been searched yet
+              </div>
+            )
+          ) : (
+            <div className="flex flex-row items-center justify-center gap-2 text-gray-400 text-sm py-4">
+              <LoaderIcon className="animate-spin size-5.5" />
+              Loading history...
+            </div>
+          )}
+        </DialogBody>
+        <DialogFooter>
+          <DialogClose render={<Button variant="outline">Close</Button>} />
+        </DialogFooter>
+      </DialogContent>
+    </Dialog>
+  );
+}
+
+function SearchHistoryItem({ search }: { search: SearchAttempt }) {
+  return (
+    <li className="flex flex-col gap-1 text-sm">
+      <div className="flex flex-row items-center gap-2">
+        <Tag variant={search.outcome === "imported" ? "primary" : "secondary"}>
+          {OUTCOME_LABELS[search.outcome]}
+        </Tag>
+        <span className="text-gray-400 text-xs">
+          {formatRelative(search.createdAt, new Date())} -{" "}
+          {search.providers.join(", ")}
+        </span>
+      </div>
+      {search.error && (
+        <div className="text-xs text-failure">{search.error}</div>
+      )}
+      {search.errors.map((error) => (
+        <div
+          key={error.provider.name}
+          className="flex flex-row items-center gap-2 text-xs text-gray-400"
+        >
+          <CircleAlertIcon className="text-failure size-4 shrink-0" />
+          <Tag>{error.provider.name}</Tag>
+          {error.error}
+        </div>
+      ))}
+      <ul className="flex flex-col text-xs">
+        {search.candidates.map((candidate) => (
+          <li
+            key={`${candidate.provider}/${candidate.identifier}`}
+            data-chosen={
+              (search.chosen?.provider === candidate.provider &&
+                search.chosen?.identifier === candidate.identifier) ||
+              undefined
+            }
+            className="flex flex-row items-center gap-2 text-gray-400 data-chosen:text-gray-100"
+            title={[
+              `Title: ${formatPercent(candidate.breakdown.trackName)}`,
+              `Artist: ${formatPercent(candidate.breakdown.artistName)}`,
+              `Album: ${formatPercent(candidate.breakdown.albumTitle)}`,
+              `Duration: ${formatPercent(candidate.breakdown.duration)}`,
+            ].join("\n")}
+          >
+            <Tag>{candidate.provider}</Tag>
+            <span className="truncate flex-1">{candidate.name}</span>
+            {candidate.blocklisted && (
+              <BanIcon className="text-failure size-3.5 shrink-0" />
+            )}
+            <span
+              data-below={candidate.score < search.minScore || undefined}
+              className="data-below:text-failure"
+            >
+              {formatPercent(candidate.score)}
+            </span>
+          </li>
+        ))}
+      </ul>
+    </li>
+  );
+}
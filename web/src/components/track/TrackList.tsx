@@ -3,12 +3,19 @@ import { tracksQueryOptions } from "@/queries/track";
 import { Route } from "@/routes/(app)/artists/$id";
 import { cn } from "@/utils/cn";
 import { useQuery } from "@tanstack/react-query";
-import { CheckIcon, SearchIcon, UserIcon, XIcon } from "lucide-react";
+import {
+  CheckIcon,
+  HistoryIcon,
+  SearchIcon,
+  UserIcon,
+  XIcon,
+} from "lucide-react";
 import type { ComponentProps } from "react";
 import { Tag } from "../ui/Tag";
 import { Button } from "../ui/Button";
 import { AutomaticSearchTrackButton } from "./AutomaticSearchTrackButton";
 import { ManualSearchDialog } from "./ManualSearchDialog";
+import { SearchHistoryDialog } from "./SearchHistoryDialog";
 import { DialogTrigger } from "../ui/Dialog";
 
 export function TrackList({
@@ -72,6 +79,20 @@ export function TrackListItem({ track }: { track: Track }) {
             }
           />
         </ManualSearchDialog>
+        <SearchHistoryDialog trackId={track.id}>
+          <DialogTrigger
+            render={
+              <Button
+                size="icon-sm"
+                variant="ghost"
+                type="button"
+                title="Search history"
+              >
+                <HistoryIcon />
+              </Button>
+            }
+          />
+        </SearchHistoryDialog>
       </div>
     </li>
   );
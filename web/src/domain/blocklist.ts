@@ -0,0 +1,7 @@
+export interface BlocklistEntry {
+  id: number;
+  createdAt: string;
+  trackId: number;
+  provider: string;
+  identifier: string;
+}
@@ -1,4 +1,5 @@
-import type { Job } from "./job";
+import type { Job, JobProgress } from "./job";
+import type { JobLogLevel } from "./jobLog";
 
 export interface JobStartEvent {
   type: "jobStart";
@@ -13,7 +14,18 @@ export interface JobEndEvent {
 export interface JobLogEvent {
   type: "jobLog";
   jobId: number;
+  level: JobLogLevel;
   log: string;
 }
 
-export type AppEvent = JobStartEvent | JobEndEvent | JobLogEvent;
+export interface JobProgressEvent {
+  type: "jobProgress";
+  jobId: number;
+  progress: JobProgress;
+}
+
+export type AppEvent =
+  | JobStartEvent
+  | JobEndEvent
+  | JobLogEvent
+  | JobProgressEvent;
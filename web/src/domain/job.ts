@@ -7,6 +7,20 @@ export interface ImportLyricsJob {
   content: string;
 }
 
+export interface ImportResultJob {
+  type: "importResult";
+  trackId: number;
+  provider: string;
+  identifier: string;
+  pin?: boolean;
+}
+
+// Publish
+export interface PublishLyricsJob {
+  type: "publishLyrics";
+  lyricsId: number;
+}
+
 // Scan
 export interface ScanLibraryJob {
   type: "scanLibrary";
@@ -47,6 +61,10 @@ export interface SearchTrackJob {
   trackId: number;
 }
 
+export interface SearchWantedJob {
+  type: "searchWanted";
+}
+
 // Sync
 export interface SyncLibraryJob {
   type: "syncLibrary";
@@ -59,6 +77,8 @@ export interface SyncArtistJob {
 
 export type JobPayload =
   | ImportLyricsJob
+  | ImportResultJob
+  | PublishLyricsJob
   | ScanLibraryJob
   | ScanArtistJob
   | ScanAlbumJob
@@ -67,15 +87,36 @@ export type JobPayload =
   | SearchArtistJob
   | SearchAlbumJob
   | SearchTrackJob
+  | SearchWantedJob
   | SyncLibraryJob
   | SyncArtistJob;
 
-export type JobStatus = "pending" | "running" | "done" | "failed";
+export type JobStatus =
+  | "pending"
+  | "running"
+  | "done"
+  | "failed"
+  | "cancelled";
+
+export type JobLane = "library" | "search" | "user";
+
+export interface JobProgress {
+  current: number;
+  total: number;
+  phase: string;
+  entity?: string;
+}
 
 export interface Job {
   id: number;
   createdAt: string;
   payload: JobPayload;
   status: JobStatus;
+  lane: JobLane;
+  priority: number;
+  attempt: number;
+  maxAttempts: number;
+  retryOf?: number;
   error?: string;
+  progress?: JobProgress;
 }
@@ -0,0 +1,9 @@
+export type JobLogLevel = "info" | "warn" | "error";
+
+export interface JobLog {
+  id: number;
+  createdAt: string;
+  jobId: number;
+  level: JobLogLevel;
+  message: string;
+}
@@ -6,6 +6,10 @@ export interface Lyrics {
   synced: boolean;
   filePath: string;
   checksum: string;
+  provider?: string;
+  providerIdentifier?: string;
+  publishedAt?: string;
+  publishError?: string;
 }
 
 export interface LyricsContent {
@@ -5,7 +5,11 @@ export interface ProviderFile {
   artistName: string;
   albumTitle: string;
   synced: boolean;
+  exact: boolean;
+  translation: boolean;
   durationMs: number;
+  isrc?: string;
+  musicbrainzId?: string;
   content: string;
 }
 
@@ -13,12 +17,42 @@ export interface ProviderMetadata {
   name: string;
 }
 
+export interface ScoreBreakdown {
+  trackName: number;
+  artistName: number;
+  albumTitle: number;
+  duration: number;
+  identifier: boolean;
+}
+
 export interface ProviderResult {
   provider: ProviderMetadata;
   file: ProviderFile;
   score: number;
+  breakdown: ScoreBreakdown;
+}
+
+export interface ProviderError {
+  provider: ProviderMetadata;
+  error: string;
+}
+
+export interface ProviderResults {
+  providers: ProviderMetadata[];
+  results: ProviderResult[];
+  errors: ProviderError[];
 }
 
 export interface ProviderResultSearch {
   trackId: number;
 }
+
+export type CircuitState = "closed" | "open" | "halfOpen";
+
+export interface ProviderStatus {
+  name: string;
+  state: CircuitState;
+  consecutiveFailures: number;
+  retryAfterSecs: number | null;
+  lastError: string | null;
+}
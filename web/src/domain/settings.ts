@@ -5,6 +5,20 @@ export const settingsSchema = z.object({
   lyrics: z.object({
     minScore: z.number().min(0).max(1),
     upgrade: z.boolean(),
+    providerTimeout: z.int(),
+    weights: z.object({
+      trackName: z.number().min(0),
+      artistName: z.number().min(0),
+      albumTitle: z.number().min(0),
+      duration: z.number().min(0),
+    }),
+    normalization: z.object({
+      lowercase: z.boolean(),
+      stripPunctuation: z.boolean(),
+      stripFeatures: z.boolean(),
+      stripSuffixes: z.boolean(),
+      stripDiacritics: z.boolean(),
+    }),
   }),
   auth: z.union([
     z.object({ enabled: z.literal(false) }),
@@ -21,6 +35,75 @@ export const settingsSchema = z.object({
     httpTimeout: z.int(),
     apiKey: z.string(),
   }),
+  cache: z.object({
+    enabled: z.boolean(),
+    hitTtl: z.int(),
+    missTtl: z.int(),
+  }),
+  wanted: z.object({
+    budget: z.int().min(0),
+    concurrency: z.int().min(1),
+    backoff: z.array(z.int().min(0)),
+  }),
+  workers: z.object({
+    library: z.int().min(1),
+    search: z.int().min(1),
+    user: z.int().min(1),
+  }),
+  jobLogs: z.object({
+    retentionDays: z.int().min(0),
+    maxPerJob: z.int().min(1),
+  }),
+  providers: z.object({
+    limits: z.object({
+      requestsPerSecond: z.number().min(0),
+      maxInFlight: z.int().min(1),
+      maxRetries: z.int().min(0),
+      retryBackoffMs: z.int().min(0),
+      failureThreshold: z.int().min(1),
+      cooldown: z.int().min(0),
+    }),
+    genius: z.object({
+      enabled: z.boolean(),
+      accessToken: z.string().nullish(),
+    }),
+    local: z.object({
+      enabled: z.boolean(),
+      path: z.string(),
+    }),
+    musixmatch: z.object({
+      enabled: z.boolean(),
+      token: z.string().nullish(),
+      enhanced: z.boolean(),
+    }),
+    netease: z.object({
+      enabled: z.boolean(),
+    }),
+    script: z.object({
+      enabled: z.boolean(),
+      path: z.string(),
+      timeout: z.int(),
+    }),
+    template: z.object({
+      enabled: z.boolean(),
+      searchUrl: z.string(),
+      resultsPointer: z.string(),
+      fields: z.object({
+        identifier: z.string(),
+        name: z.string(),
+        trackName: z.string(),
+        artistName: z.string(),
+        albumTitle: z.string(),
+        duration: z.string(),
+        durationUnit: z.enum(["seconds", "milliseconds"]),
+        isrc: z.string(),
+        musicbrainzId: z.string(),
+        syncedLyrics: z.string(),
+        plainLyrics: z.string(),
+      }),
+      downloadUrl: z.string(),
+    }),
+  }),
 });
 
 export type Settings = z.infer<typeof settingsSchema>;
@@ -7,7 +7,11 @@ export interface Track {
   id: number;
   trackNumber: number;
   title: string;
+  musicbrainzId?: string;
+  recordingMusicbrainzId?: string;
+  isrc?: string;
   hasLyrics: boolean;
+  lyricsPinned: boolean;
   artist: Artist;
   album: Album;
 }
@@ -0,0 +1,37 @@
+import type { ProviderError, ScoreBreakdown } from "./provider";
+
+export type SearchOutcome =
+  | "imported"
+  | "importFailed"
+  | "noResults"
+  | "providerError"
+  | "belowMinScore"
+  | "blocklisted";
+
+export interface SearchCandidate {
+  provider: string;
+  identifier: string;
+  name: string;
+  trackName: string;
+  artistName: string;
+  albumTitle: string;
+  synced: boolean;
+  durationMs?: number;
+  score: number;
+  breakdown: ScoreBreakdown;
+  blocklisted: boolean;
+}
+
+export interface SearchAttempt {
+  id: number;
+  createdAt: string;
+  trackId: number;
+  jobId?: number;
+  outcome: SearchOutcome;
+  minScore: number;
+  providers: string[];
+  candidates: SearchCandidate[];
+  errors: ProviderError[];
+  chosen?: SearchCandidate;
+  error?: string;
+}
@@ -142,6 +142,10 @@ function jobToNotification(job: Job) {
       duration = 1_000;
       status = "success";
       break;
+    case "cancelled":
+      duration = 2_000;
+      status = "default";
+      break;
   }
   return {
     title: JobNotificationTitle({ job }),
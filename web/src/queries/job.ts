@@ -1,6 +1,7 @@
 import type { AppEvent } from "@/domain/event";
 import type { Page, Pageable } from "@/domain/generic";
 import type { Job, JobPayload } from "@/domain/job";
+import type { JobLog } from "@/domain/jobLog";
 import { useSocketState } from "@/hooks/socket/useSocketState";
 import { fetchApi } from "@/utils/api";
 import { invalidateAll } from "@/utils/query";
@@ -24,47 +25,85 @@ export function jobQueryOptions(id: number) {
   });
 }
 
-export function createJobMutationOptions() {
-  return mutationOptions({
-    mutationFn: async (body: JobPayload) => {
-      const buffered: AppEvent[] = [];
-      let jobId: number | null = null;
+export function jobLogsQueryOptions(id: number, query?: Pageable) {
+  return queryOptions({
+    queryKey: ["jobs", "details", id, "logs", query],
+    queryFn: () => {
+      return fetchApi<Page<JobLog>>(`jobs/${id}/logs`, { query });
+    },
+  });
+}
 
-      return new Promise<void>((resolve, reject) => {
-        const handleEvent = (e: AppEvent) => {
-          console.log("handleEvent", e, jobId)
-          if (e.type === "jobEnd" && e.job.id === jobId) {
-            unsubscribe();
-            resolve();
-          }
-        };
+/** Sends a request that enqueues a job and resolves once that job has ended. */
+export function awaitJob(request: () => Promise<Job>) {
+  const buffered: AppEvent[] = [];
+  let jobId: number | null = null;
 
-        const unsubscribe = useSocketState.getState().listen((e) => {
-          if (jobId === null) {
-            buffered.push(e);
-          } else {
-            handleEvent(e);
-          }
-        });
+  return new Promise<Job>((resolve, reject) => {
+    const handleEvent = (e: AppEvent) => {
+      console.log("handleEvent", e, jobId)
+      if (e.type === "jobEnd" && e.job.id === jobId) {
+        unsubscribe();
+        resolve(e.job);
+      }
+    };
 
+    const unsubscribe = useSocketState.getState().listen((e) => {
+      if (jobId === null) {
+        buffered.push(e);
+      } else {
+        handleEvent(e);
+      }
+    });
+
+    request()
+      .then((job) => {
+        jobId = job.id;
+        for (const e of buffered) {
+          handleEvent(e);
+        }
+      })
+      .catch((e) => {
+        unsubscribe();
+        reject(e);
+      });
+  });
+}
+
+export function createJobMutationOptions() {
+  return mutationOptions({
+    mutationFn: async (body: JobPayload) => {
+      await awaitJob(() =>
         fetchApi<Job>("/jobs", {
           method: "POST",
           json: body,
         })
-          .then((job) => {
-            jobId = job.id;
-            for (const e of buffered) {
-              handleEvent(e);
-            }
-          })
-          .catch((e) => {
-            unsubscribe();
-            reject(e);
-          });
-      });
+      );
     },
     onSettled: async (_1, _2, _3, _4, { client }) => {
       await invalidateAll(client);
     },
   });
 }
+
+export function cancelJobMutationOptions() {
+  return mutationOptions({
+    mutationFn: async (id: number) => {
+      return fetchApi<Job>(`jobs/${id}/cancel`, { method: "POST" });
+    },
+    onSettled: async (_1, _2, _3, _4, { client }) => {
+      await client.invalidateQueries({ queryKey: ["jobs"] });
+    },
+  });
+}
+
+export function retryJobMutationOptions() {
+  return mutationOptions({
+    mutationFn: async (id: number) => {
+      return fetchApi<Job>(`jobs/${id}/retry`, { method: "POST" });
+    },
+    onSettled: async (_1, _2, _3, _4, { client }) => {
+      await client.invalidateQueries({ queryKey: ["jobs"] });
+    },
+  });
+}
@@ -1,5 +1,7 @@
 import type { Page } from "@/domain/generic";
+import type { Job } from "@/domain/job";
 import type { Lyrics, LyricsContent, LyricsSearch } from "@/domain/lyrics";
+import { awaitJob } from "@/queries/job";
 import { fetchApi } from "@/utils/api";
 import { invalidateAll } from "@/utils/query";
 import { mutationOptions, queryOptions } from "@tanstack/react-query";
@@ -24,10 +26,13 @@ export function lyricQueryOptions(id: number) {
 
 export function deleteLyricMutationOptions() {
   return mutationOptions({
-    mutationFn: (id: number) => {
-      return fetchApi<Lyrics>(`lyrics/${id}`, { method: "DELETE" });
+    mutationFn: ({ id, blocklist }: { id: number; blocklist?: boolean }) => {
+      return fetchApi<Lyrics>(`lyrics/${id}`, {
+        method: "DELETE",
+        query: blocklist ? { blocklist } : undefined,
+      });
     },
-    onSettled: async (_data, _error, id, _onResult, { client }) => {
+    onSettled: async (_data, _error, { id }, _onResult, { client }) => {
       client.removeQueries({
         queryKey: ["lyrics", "details", id],
       });
@@ -39,6 +44,24 @@ export function deleteLyricMutationOptions() {
   });
 }
 
+export function publishLyricMutationOptions() {
+  return mutationOptions({
+    mutationFn: async (id: number) => {
+      const job = await awaitJob(() =>
+        fetchApi<Job>(`lyrics/${id}/publish`, { method: "POST" })
+      );
+      if (job.status === "failed") {
+        throw new Error(job.error);
+      }
+    },
+    onSettled: async (_data, _error, id, _onResult, { client }) => {
+      await client.invalidateQueries({
+        queryKey: ["lyrics", "details", id],
+      });
+    },
+  });
+}
+
 export function lyricContentQueryOptions(id: number) {
   return queryOptions({
     queryKey: ["lyrics", "content", id],
@@ -1,15 +1,39 @@
-import type { ProviderResult, ProviderResultSearch } from "@/domain/provider";
+import type {
+  ProviderResults,
+  ProviderResultSearch,
+  ProviderStatus,
+} from "@/domain/provider";
 import { fetchApi } from "@/utils/api";
-import { queryOptions } from "@tanstack/react-query";
+import { mutationOptions, queryOptions } from "@tanstack/react-query";
 
 export function getProvidersResultsQueryOption(query: ProviderResultSearch) {
   return queryOptions({
     queryKey: ["providers", "results", query],
     queryFn: () => {
-      return fetchApi<ProviderResult[]>("providers/results", { query });
+      return fetchApi<ProviderResults>("providers/results", { query });
     },
     refetchOnMount: false,
     refetchOnReconnect: false,
     refetchOnWindowFocus: false,
   });
 }
+
+export function purgeProviderCacheMutationOptions() {
+  return mutationOptions({
+    mutationFn: () => {
+      return fetchApi<void>("providers/cache", { method: "DELETE" });
+    },
+    onSuccess: (_data, _variables, _onResult, { client }) => {
+      client.removeQueries({ queryKey: ["providers", "results"] });
+    },
+  });
+}
+
+export function providerStatusesQueryOptions() {
+  return queryOptions({
+    queryKey: ["providers", "statuses"],
+    queryFn: () => {
+      return fetchApi<ProviderStatus[]>("providers");
+    },
+  });
+}
@@ -1,7 +1,9 @@
-import type { Page } from "@/domain/generic";
+import type { BlocklistEntry } from "@/domain/blocklist";
+import type { Page, Pageable } from "@/domain/generic";
 import type { Track, TrackSearch } from "@/domain/track";
+import type { SearchAttempt } from "@/domain/trackSearch";
 import { fetchApi } from "@/utils/api";
-import { queryOptions } from "@tanstack/react-query";
+import { mutationOptions, queryOptions } from "@tanstack/react-query";
 
 export function tracksQueryOptions(query?: TrackSearch) {
   return queryOptions({
@@ -20,3 +22,41 @@ export function trackQueryOptions(id: number) {
     },
   });
 }
+
+export function trackSearchesQueryOptions(id: number, query?: Pageable) {
+  return queryOptions({
+    queryKey: ["tracks", id, "searches", query],
+    queryFn: () => {
+      return fetchApi<Page<SearchAttempt>>(`tracks/${id}/searches`, {
+        query,
+      });
+    },
+  });
+}
+
+export function trackBlocklistQueryOptions(id: number) {
+  return queryOptions({
+    queryKey: ["tracks", id, "blocklist"],
+    queryFn: () => {
+      return fetchApi<BlocklistEntry[]>(`tracks/${id}/blocklist`);
+    },
+  });
+}
+
+export function clearTrackBlocklistMutationOptions(id: number) {
+  return mutationOptions({
+    mutationFn: (entryId?: number) => {
+      return fetchApi<void>(
+        entryId === undefined
+          ? `tracks/${id}/blocklist`
+          : `tracks/${id}/blocklist/${entryId}`,
+        { method: "DELETE" }
+      );
+    },
+    onSettled: async (_data, _error, _variables, _onResult, { client }) => {
+      await client.invalidateQueries({
+        queryKey: ["tracks", id, "blocklist"],
+      });
+    },
+  });
+}
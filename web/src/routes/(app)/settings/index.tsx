@@ -8,13 +8,14 @@ import {
   BannerTitle,
 } from "@/components/ui/Banner";
 import { toastPromise } from "@/hooks/notification/useNotificationState";
+import { purgeProviderCacheMutationOptions } from "@/queries/provider";
 import {
   setSettingsMutationOptions,
   settingsQueryOptions,
 } from "@/queries/settings";
 import { useMutation, useQuery } from "@tanstack/react-query";
 import { createFileRoute } from "@tanstack/react-router";
-import { SaveIcon } from "lucide-react";
+import { SaveIcon, Trash2Icon } from "lucide-react";
 import { useId } from "react";
 
 export const Route = createFileRoute("/(app)/settings/")({
@@ -23,6 +24,7 @@ export const Route = createFileRoute("/(app)/settings/")({
 
 function RouteComponent() {
   const formId = useId();
+  const purgeCache = useMutation(purgeProviderCacheMutationOptions());
 
   return (
     <>
@@ -32,6 +34,17 @@ function RouteComponent() {
             <SaveIcon />
             Save
           </BannerAction>
+          <BannerAction
+            onClick={() =>
+              toastPromise(purgeCache.mutateAsync(), {
+                title: "Purging cache",
+                success: "Cache purged",
+              })
+            }
+          >
+            <Trash2Icon />
+            Purge cache
+          </BannerAction>
         </BannerActions>
         <BannerContent>
           <BannerHeader>